@@ -15,6 +15,7 @@
 #[cfg(feature = "std")]
 use std::error::Error;
 use std::fmt::{Display, Formatter, self};
+use std::str;
 
 // UTF-8 ranges and tags for encoding characters
 const TAG_CONT: u8    = 0b1000_0000;
@@ -81,3 +82,68 @@ pub fn encode_utf8(ch: char, buf: &mut [u8]) -> Result<usize, EncodeUtf8Error>
     Err(EncodeUtf8Error(()))
 }
 
+/// Encode a char into the start of `buf` using UTF-8, and return the
+/// encoded part of `buf` as a string.
+///
+/// Return `None` if the buffer was too short for the char.
+///
+/// ```
+/// use odds::char::encode_char;
+///
+/// let mut frame = [0; 8];
+/// assert_eq!(encode_char('€', &mut frame[2..]), Some("€"));
+/// assert_eq!(&frame[2..5], "€".as_bytes());
+/// assert_eq!(encode_char('€', &mut frame[..2]), None);
+/// ```
+pub fn encode_char(ch: char, buf: &mut [u8]) -> Option<&str> {
+    match encode_utf8(ch, buf) {
+        Ok(n) => unsafe { Some(str::from_utf8_unchecked(&buf[..n])) },
+        Err(_) => None,
+    }
+}
+
+/// Decode the first char of the UTF-8 encoded `bytes`.
+///
+/// Return the char and its byte length, or `None` if `bytes` is empty or
+/// does not start with a complete, valid UTF-8 sequence.
+///
+/// ```
+/// use odds::char::decode_first_char;
+///
+/// assert_eq!(decode_first_char("€uro".as_bytes()), Some(('€', 3)));
+/// assert_eq!(decode_first_char(b"\xe2\x82"), None);
+/// assert_eq!(decode_first_char(b"\xff"), None);
+/// ```
+pub fn decode_first_char(bytes: &[u8]) -> Option<(char, usize)> {
+    let width = match bytes.first() {
+        None => return None,
+        Some(&b) if b < 0x80 => return Some((b as char, 1)),
+        Some(&b) if b < 0xC2 => return None,
+        Some(&b) if b < 0xE0 => 2,
+        Some(&b) if b < 0xF0 => 3,
+        Some(&b) if b < 0xF5 => 4,
+        Some(_) => return None,
+    };
+    // let libcore reject overlong encodings and surrogates
+    match bytes.get(..width).map(str::from_utf8) {
+        Some(Ok(s)) => s.chars().next().map(|c| (c, width)),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_encode_decode() {
+    let mut buf = [0; 4];
+    for &c in &['\0', 'a', '\x7f', '\u{80}', 'ß', '\u{7ff}', '\u{800}', '€', '\u{ffff}',
+                '\u{10000}', '𝄞', '\u{10ffff}'] {
+        let n = c.len_utf8();
+        assert_eq!(encode_char(c, &mut buf[..n - 1]), None);
+        assert_eq!(encode_char(c, &mut buf), Some(&*c.to_string()));
+        assert_eq!(decode_first_char(&buf[..n]), Some((c, n)));
+        assert_eq!(decode_first_char(&buf[..n - 1]), None);
+    }
+    for bytes in &[&b"\x80"[..], b"\xc0\x80", b"\xe0\x80\x80", b"\xed\xa0\x80",
+                   b"\xf4\x90\x80\x80", b"\xf8\x80\x80\x80", b"\xc3a", b""] {
+        assert_eq!(decode_first_char(bytes), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Edit distances between strings and slices
+//!
+//! The functions in this module allocate a buffer of one or two rows of
+//! the distance table, each as long as the second argument.
+//!
+//! Requires `feature="std"`
+
+use std::cmp::min;
+use std::mem;
+
+/// Return the Levenshtein distance between `a` and `b`: the least number
+/// of insertions, deletions and substitutions of one element that turn
+/// `a` into `b`.
+///
+/// ```
+/// use odds::distance::edit_distance;
+///
+/// assert_eq!(edit_distance(b"kitten", b"sitting"), 3);
+/// assert_eq!(edit_distance(&[1, 2, 3], &[1, 3]), 1);
+/// ```
+pub fn edit_distance<T>(a: &[T], b: &[T]) -> usize
+    where T: PartialEq
+{
+    levenshtein(a.iter(), b.iter(), !0).unwrap()
+}
+
+/// Return the Levenshtein distance between `a` and `b` if it is at most
+/// `max`, or `None` otherwise.
+///
+/// Only the part of the table within `max` of the diagonal is computed,
+/// and the computation stops as soon as the distance is known to be
+/// greater than `max`, so this is much faster than `edit_distance` for
+/// small `max`.
+///
+/// ```
+/// use odds::distance::edit_distance_within;
+///
+/// assert_eq!(edit_distance_within(b"kitten", b"sitting", 3), Some(3));
+/// assert_eq!(edit_distance_within(b"kitten", b"sitting", 2), None);
+/// ```
+pub fn edit_distance_within<T>(a: &[T], b: &[T], max: usize) -> Option<usize>
+    where T: PartialEq
+{
+    levenshtein(a.iter(), b.iter(), max)
+}
+
+/// Return the Damerau-Levenshtein distance between `a` and `b`, which
+/// also counts swapping two adjacent elements as one edit.
+///
+/// This is the restricted variant (optimal string alignment distance): a
+/// swapped pair is not edited again.
+///
+/// ```
+/// use odds::distance::{damerau_edit_distance, edit_distance};
+///
+/// assert_eq!(damerau_edit_distance(b"form", b"from"), 1);
+/// assert_eq!(edit_distance(b"form", b"from"), 2);
+/// ```
+pub fn damerau_edit_distance<T>(a: &[T], b: &[T]) -> usize
+    where T: PartialEq
+{
+    osa(a.iter(), b.iter())
+}
+
+/// Return the Levenshtein distance between `a` and `b`, counted in chars.
+///
+/// ```
+/// use odds::distance::edit_distance_str;
+///
+/// assert_eq!(edit_distance_str("café", "cafe"), 1);
+/// ```
+pub fn edit_distance_str(a: &str, b: &str) -> usize {
+    levenshtein(a.chars(), b.chars(), !0).unwrap()
+}
+
+/// Return the Levenshtein distance between `a` and `b`, counted in chars,
+/// if it is at most `max`, or `None` otherwise.
+///
+/// See [`edit_distance_within`](fn.edit_distance_within.html).
+///
+/// ```
+/// use odds::distance::edit_distance_within_str;
+///
+/// let commands = ["build", "bench", "check", "clean"];
+/// let typo = "chekc";
+/// let suggestions = commands.iter()
+///     .filter(|c| edit_distance_within_str(typo, c, 2).is_some())
+///     .collect::<Vec<_>>();
+/// assert_eq!(suggestions, [&"check"]);
+/// ```
+pub fn edit_distance_within_str(a: &str, b: &str, max: usize) -> Option<usize> {
+    levenshtein(a.chars(), b.chars(), max)
+}
+
+/// Return the Damerau-Levenshtein distance between `a` and `b`, counted
+/// in chars.
+///
+/// See [`damerau_edit_distance`](fn.damerau_edit_distance.html).
+pub fn damerau_edit_distance_str(a: &str, b: &str) -> usize {
+    osa(a.chars(), b.chars())
+}
+
+/// Levenshtein distance, or `None` if it is greater than `max`.
+///
+/// Cells further than `max` from the diagonal are never computed; they
+/// hold `max + 1`, which stands for any value greater than `max`.
+fn levenshtein<A, B>(a: A, b: B, max: usize) -> Option<usize>
+    where A: Iterator + Clone,
+          B: Iterator<Item=A::Item>,
+          A::Item: PartialEq
+{
+    // collected so that each row can start at the band, which walking a
+    // `Chars` from the start would make quadratic
+    let b = b.collect::<Vec<_>>();
+    let n = a.clone().count();
+    let m = b.len();
+    // each insertion or deletion changes the length by one
+    if (n > m && n - m > max) || (m > n && m - n > max) {
+        return None;
+    }
+    let inf = max.saturating_add(1);
+    let mut row = (0..m + 1).map(|j| min(j, inf)).collect::<Vec<_>>();
+    let mut i = 0;
+    for x in a {
+        i += 1;
+        let lo = if i > max { i - max } else { 1 };
+        let hi = min(m, i.saturating_add(max));
+        let mut diag = row[lo - 1];
+        row[lo - 1] = if lo == 1 { min(i, inf) } else { inf };
+        let mut row_min = row[lo - 1];
+        for j in lo - 1..hi {
+            let cost = (x != b[j]) as usize;
+            let value = min(min(row[j], row[j + 1]).saturating_add(1), diag + cost);
+            diag = row[j + 1];
+            row[j + 1] = min(value, inf);
+            row_min = min(row_min, row[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+    }
+    if row[m] <= max { Some(row[m]) } else { None }
+}
+
+/// Optimal string alignment distance.
+fn osa<A, B>(a: A, b: B) -> usize
+    where A: Iterator,
+          B: Iterator<Item=A::Item> + Clone,
+          A::Item: PartialEq + Clone
+{
+    let m = b.clone().count();
+    let mut prev2 = vec![0; m + 1];
+    let mut prev = (0..m + 1).collect::<Vec<_>>();
+    let mut cur = vec![0; m + 1];
+    let mut last_x = None;
+    for (i, x) in a.enumerate() {
+        cur[0] = i + 1;
+        let mut last_y = None;
+        for (j, y) in b.clone().enumerate() {
+            let cost = (x != y) as usize;
+            let mut value = min(min(prev[j + 1], cur[j]) + 1, prev[j] + cost);
+            if let (Some(lx), Some(ly)) = (last_x.as_ref(), last_y.as_ref()) {
+                if x == *ly && *lx == y {
+                    value = min(value, prev2[j - 1] + 1);
+                }
+            }
+            cur[j + 1] = value;
+            last_y = Some(y);
+        }
+        mem::swap(&mut prev2, &mut prev);
+        mem::swap(&mut prev, &mut cur);
+        last_x = Some(x);
+    }
+    prev[m]
+}
+
+#[test]
+fn test_edit_distance() {
+    use random::{RandomSource, XorShift};
+
+    // the full table, as in the textbook
+    fn naive(a: &[u8], b: &[u8], transpose: bool) -> usize {
+        let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() + 1 {
+            for j in 0..b.len() + 1 {
+                d[i][j] = if i == 0 || j == 0 { i + j } else {
+                    let cost = (a[i - 1] != b[j - 1]) as usize;
+                    min(min(d[i - 1][j], d[i][j - 1]) + 1, d[i - 1][j - 1] + cost)
+                };
+                if transpose && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    d[i][j] = min(d[i][j], d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    let mut rng = XorShift::new(31);
+    for _ in 0..500 {
+        let a = (0..rng.below(10)).map(|_| b'a' + rng.below(3) as u8).collect::<Vec<_>>();
+        let b = (0..rng.below(10)).map(|_| b'a' + rng.below(3) as u8).collect::<Vec<_>>();
+        let d = naive(&a, &b, false);
+        assert_eq!(edit_distance(&a, &b), d);
+        assert_eq!(damerau_edit_distance(&a, &b), naive(&a, &b, true));
+        for max in 0..12 {
+            let expected = if d <= max { Some(d) } else { None };
+            assert_eq!(edit_distance_within(&a, &b, max), expected,
+                       "{:?} {:?} {}", a, b, max);
+        }
+        let (s, t) = (String::from_utf8(a).unwrap(), String::from_utf8(b).unwrap());
+        let (s, t) = (s.replace('a', "α"), t.replace('a', "α"));
+        assert_eq!(edit_distance_str(&s, &t), d);
+        assert_eq!(edit_distance_within_str(&s, &t, 2), if d <= 2 { Some(d) } else { None });
+    }
+    assert_eq!(edit_distance_within_str("", "", 0), Some(0));
+    assert_eq!(edit_distance_within_str("abcdef", "", 3), None);
+    assert_eq!(damerau_edit_distance_str("ca", "abc"), 3);
+}
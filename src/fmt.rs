@@ -0,0 +1,790 @@
+//! Formatting helpers that don't allocate
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::marker::PhantomData;
+use std::mem::{size_of, MaybeUninit};
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+use std::str;
+
+use string::StrExt;
+use utf8::lossy_runs;
+
+/// Error returned when a value does not fit in its fixed-width field, or
+/// when the field could not be written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Overflow(());
+
+impl Display for Overflow {
+    #[inline]
+    fn fmt(&self, fmtr: &mut Formatter) -> fmt::Result {
+        fmtr.pad(self.description())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Overflow {
+    #[inline]
+    fn description(&self) -> &str {
+        Overflow::description(self)
+    }
+}
+
+impl Overflow {
+    #[inline]
+    pub fn description(&self) -> &str {
+        "the formatted value did not fit in the fixed-width field"
+    }
+}
+
+/// A writer that only counts the chars written to it.
+struct CharCount(usize);
+
+impl Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Return the width of `value` when formatted, in chars.
+fn display_width<T: Display>(value: &T) -> usize {
+    let mut count = CharCount(0);
+    let _ = write!(count, "{}", value);
+    count.0
+}
+
+fn write_pad<W: Write>(out: &mut W, pad: char, n: usize) -> fmt::Result {
+    for _ in 0..n {
+        try!(out.write_char(pad));
+    }
+    Ok(())
+}
+
+/// Write `value` right-aligned in a field of `width` chars, filling the
+/// space on the left with `pad`.
+///
+/// Return `Err(Overflow)` if `value` is wider than `width`, in which
+/// case nothing is written, or if writing to `out` fails.
+///
+/// ```
+/// use odds::fmt::format_fixed_width;
+///
+/// let mut s = String::new();
+/// format_fixed_width(42, 6, ' ', &mut s).unwrap();
+/// format_fixed_width(7, 3, '0', &mut s).unwrap();
+/// assert_eq!(s, "    42007");
+/// assert!(format_fixed_width(1234, 3, ' ', &mut s).is_err());
+/// assert_eq!(s, "    42007");
+/// ```
+pub fn format_fixed_width<T, W>(value: T, width: usize, pad: char, out: &mut W)
+    -> Result<(), Overflow>
+    where T: Display,
+          W: Write,
+{
+    let len = display_width(&value);
+    if len > width {
+        return Err(Overflow(()));
+    }
+    write_pad(out, pad, width - len)
+        .and_then(|_| write!(out, "{}", value))
+        .map_err(|_| Overflow(()))
+}
+
+/// Write `value` left-aligned in a field of `width` chars, filling the
+/// space on the right with `pad`.
+///
+/// Return `Err(Overflow)` if `value` is wider than `width`, in which
+/// case nothing is written, or if writing to `out` fails.
+///
+/// ```
+/// use odds::fmt::format_fixed_width_left;
+///
+/// let mut s = String::new();
+/// format_fixed_width_left("id", 4, '.', &mut s).unwrap();
+/// assert_eq!(s, "id..");
+/// ```
+pub fn format_fixed_width_left<T, W>(value: T, width: usize, pad: char, out: &mut W)
+    -> Result<(), Overflow>
+    where T: Display,
+          W: Write,
+{
+    let len = display_width(&value);
+    if len > width {
+        return Err(Overflow(()));
+    }
+    write!(out, "{}", value)
+        .and_then(|_| write_pad(out, pad, width - len))
+        .map_err(|_| Overflow(()))
+}
+
+/// Write `value` right-aligned in a field of `width` chars, filling the
+/// space on the left with `pad`.
+///
+/// If `value` is wider than `width`, fill the whole field with `overflow`
+/// instead, so that a truncated value can't be mistaken for a real one.
+///
+/// ```
+/// use odds::fmt::format_fixed_width_or_fill;
+///
+/// let mut s = String::new();
+/// format_fixed_width_or_fill(99, 3, ' ', '#', &mut s).unwrap();
+/// format_fixed_width_or_fill(1000, 3, ' ', '#', &mut s).unwrap();
+/// assert_eq!(s, " 99###");
+/// ```
+pub fn format_fixed_width_or_fill<T, W>(value: T, width: usize, pad: char,
+                                        overflow: char, out: &mut W)
+    -> fmt::Result
+    where T: Display,
+          W: Write,
+{
+    let len = display_width(&value);
+    if len > width {
+        write_pad(out, overflow, width)
+    } else {
+        try!(write_pad(out, pad, width - len));
+        write!(out, "{}", value)
+    }
+}
+
+/// Write the chars of `bytes`, escaped by `escape`, and the invalid UTF-8 bytes
+/// as `\xNN`.
+fn write_escaped<I, F>(f: &mut Formatter, bytes: &[u8], mut escape: F) -> fmt::Result
+    where I: Iterator<Item=char> + ExactSizeIterator + Display,
+          F: FnMut(char) -> I,
+{
+    for run in lossy_runs(bytes) {
+        match run {
+            Ok(s) => {
+                // write the chars that are their own escape in one go
+                let mut plain = 0;
+                for (i, c) in s.char_indices() {
+                    let e = escape(c);
+                    if e.len() != 1 {
+                        try!(f.write_str(&s[plain..i]));
+                        try!(write!(f, "{}", e));
+                        plain = i + c.len_utf8();
+                    }
+                }
+                try!(f.write_str(&s[plain..]));
+            }
+            Err(invalid) => {
+                for b in invalid {
+                    try!(write!(f, "\\x{:02x}", b));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Return a wrapper that displays `text` with each char escaped like
+/// `char::escape_default`, without allocating.
+///
+/// `text` is a `str` or bytes; bytes that are not valid UTF-8 are written
+/// as `\xNN`.
+///
+/// ```
+/// use odds::fmt::escape_default;
+///
+/// assert_eq!(escape_default("tab\t\"é\"").to_string(), "tab\\t\\\"\\u{e9}\\\"");
+/// assert_eq!(escape_default(b"a\0\xff").to_string(), "a\\u{0}\\xff");
+/// ```
+pub fn escape_default<'a, T>(text: &'a T) -> EscapeDefault<'a>
+    where T: AsRef<[u8]> + ?Sized
+{
+    EscapeDefault { bytes: text.as_ref() }
+}
+
+/// Return a wrapper that displays `text` with each char escaped like
+/// `char::escape_debug`, without allocating.
+///
+/// Unlike `escape_default`, printable non-ASCII chars are written as they
+/// are. `text` is a `str` or bytes; bytes that are not valid UTF-8 are
+/// written as `\xNN`.
+///
+/// ```
+/// use odds::fmt::escape_debug;
+///
+/// let input = b"user \xce\xb1\n\xc0";
+/// assert_eq!(format!("got {}", escape_debug(input)), "got user α\\n\\xc0");
+/// ```
+pub fn escape_debug<'a, T>(text: &'a T) -> EscapeDebug<'a>
+    where T: AsRef<[u8]> + ?Sized
+{
+    EscapeDebug { bytes: text.as_ref() }
+}
+
+/// A string or bytes that display escaped like `char::escape_default`.
+///
+/// See [`escape_default()`](fn.escape_default.html) for more information.
+#[derive(Copy, Clone, Debug)]
+pub struct EscapeDefault<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Display for EscapeDefault<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_escaped(f, self.bytes, char::escape_default)
+    }
+}
+
+/// A string or bytes that display escaped like `char::escape_debug`.
+///
+/// See [`escape_debug()`](fn.escape_debug.html) for more information.
+#[derive(Copy, Clone, Debug)]
+pub struct EscapeDebug<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Display for EscapeDebug<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_escaped(f, self.bytes, char::escape_debug)
+    }
+}
+
+/// Primitive integer types, for `format_int`.
+///
+/// This trait is sealed: it is implemented for the primitive integers only,
+/// since `format_int` relies on `write_decimal` writing ASCII.
+pub trait FormatInt : Copy + sealed::Sealed {
+    /// The length of the longest decimal representation of the type.
+    const MAX_LEN: usize;
+    #[doc(hidden)]
+    fn write_decimal(self, buf: &mut [u8]) -> usize;
+}
+
+mod sealed {
+    pub trait Sealed { }
+}
+
+macro_rules! impl_format_int {
+    (@signed $($t:ty, $u:ty, $len:expr;)+) => {
+        $(
+        impl sealed::Sealed for $t { }
+        impl FormatInt for $t {
+            const MAX_LEN: usize = $len;
+            #[inline]
+            fn write_decimal(self, buf: &mut [u8]) -> usize {
+                // the minimum wraps to itself, which is right as unsigned
+                let magnitude = if self < 0 { self.wrapping_neg() } else { self } as $u;
+                let mut start = magnitude.write_decimal(buf);
+                if self < 0 {
+                    start -= 1;
+                    buf[start] = b'-';
+                }
+                start
+            }
+        }
+        )+
+    };
+    ($($t:ty, $len:expr;)+) => {
+        $(
+        impl sealed::Sealed for $t { }
+        impl FormatInt for $t {
+            const MAX_LEN: usize = $len;
+            #[inline]
+            fn write_decimal(self, buf: &mut [u8]) -> usize {
+                write_u64(self as u64, buf)
+            }
+        }
+        )+
+    };
+}
+// the pointer-sized types are as long as the fixed-size types of their width
+const USIZE_LEN: usize = match size_of::<usize>() { 2 => 5, 4 => 10, _ => 20 };
+const ISIZE_LEN: usize = match size_of::<isize>() { 2 => 6, 4 => 11, _ => 20 };
+
+impl_format_int!{u8, 3; u16, 5; u32, 10; u64, 20; usize, USIZE_LEN;}
+impl_format_int!{@signed i8, u8, 4; i16, u16, 6; i32, u32, 11; i64, u64, 20;
+                 isize, usize, ISIZE_LEN; i128, u128, 40;}
+
+impl sealed::Sealed for u128 { }
+impl FormatInt for u128 {
+    const MAX_LEN: usize = 39;
+    fn write_decimal(self, buf: &mut [u8]) -> usize {
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+        let mut n = self;
+        let mut end = buf.len();
+        while n > !0u64 as u128 {
+            // write the low 19 digits, padded with zeros
+            let start = write_u64((n % CHUNK) as u64, &mut buf[..end]);
+            for b in &mut buf[end - 19..start] {
+                *b = b'0';
+            }
+            end -= 19;
+            n /= CHUNK;
+        }
+        write_u64(n as u64, &mut buf[..end])
+    }
+}
+
+/// The decimal digits of 0 to 99, two bytes each.
+static DIGIT_PAIRS: &'static [u8; 200] = b"\
+    0001020304050607080910111213141516171819\
+    2021222324252627282930313233343536373839\
+    4041424344454647484950515253545556575859\
+    6061626364656667686970717273747576777879\
+    8081828384858687888990919293949596979899";
+
+/// Write `n` in decimal at the end of `buf`, and return where it starts.
+#[inline]
+fn write_u64(mut n: u64, buf: &mut [u8]) -> usize {
+    let mut i = buf.len();
+    while n >= 100 {
+        let pair = (n % 100) as usize * 2;
+        n /= 100;
+        i -= 2;
+        buf[i..i + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    }
+    if n >= 10 {
+        let pair = n as usize * 2;
+        i -= 2;
+        buf[i..i + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    } else {
+        i -= 1;
+        buf[i] = b'0' + n as u8;
+    }
+    i
+}
+
+/// Format `value` in decimal into `buf`, and return the formatted part of
+/// it.
+///
+/// This writes the digits two at a time, without going through
+/// `fmt::Formatter`.
+///
+/// `N` must be at least `T::MAX_LEN`, the length of the longest value of
+/// the type (20 for `u64`, and 11 for `i32`, for example); a shorter buffer
+/// is a compile time error.
+///
+/// ```
+/// use odds::fmt::{format_int, FormatInt};
+///
+/// let mut buf = [0; u64::MAX_LEN];
+/// assert_eq!(format_int(1234567u64, &mut buf), "1234567");
+/// let mut buf = [0; 4];
+/// assert_eq!(format_int(-128i8, &mut buf), "-128");
+/// ```
+///
+/// ```compile_fail
+/// use odds::fmt::format_int;
+///
+/// let mut buf = [0; 3];
+/// format_int(-128i8, &mut buf);
+/// ```
+pub fn format_int<T, const N: usize>(value: T, buf: &mut [u8; N]) -> &str
+    where T: FormatInt
+{
+    let () = BufLen::<T, N>::CHECK;
+    let start = value.write_decimal(buf);
+    // only ASCII digits and '-' were written
+    unsafe {
+        str::from_utf8_unchecked(&buf[start..])
+    }
+}
+
+/// Compile time check of the buffer length for `format_int`.
+struct BufLen<T, const N: usize>(PhantomData<T>);
+
+impl<T: FormatInt, const N: usize> BufLen<T, N> {
+    const CHECK: () = assert!(N >= T::MAX_LEN,
+                              "format_int: buffer too short for the integer type");
+}
+
+/// Return a wrapper that displays the elements of `iter` separated by
+/// `sep`, without collecting them into a string first.
+///
+/// The format specification, like a precision or width, applies to each
+/// element; the separator is written as it is. The iterator is cloned each
+/// time the wrapper is formatted.
+///
+/// ```
+/// use odds::fmt::join_display;
+///
+/// let ids = [3, 5, 8];
+/// assert_eq!(format!("ids=[{}]", join_display(&ids, ", ")), "ids=[3, 5, 8]");
+/// assert_eq!(format!("{:.1}", join_display(&[0.25, 1.0], ';')), "0.2;1.0");
+/// ```
+pub fn join_display<I, S>(iter: I, sep: S) -> JoinDisplay<I::IntoIter, S>
+    where I: IntoIterator,
+          I::IntoIter: Clone,
+          I::Item: Display,
+          S: Display,
+{
+    JoinDisplay {
+        iter: iter.into_iter(),
+        sep: sep,
+    }
+}
+
+/// The elements of an iterator, displayed with a separator.
+///
+/// See [`join_display()`](fn.join_display.html) for more information.
+#[derive(Clone, Debug)]
+pub struct JoinDisplay<I, S> {
+    iter: I,
+    sep: S,
+}
+
+impl<I, S> Display for JoinDisplay<I, S>
+    where I: Iterator + Clone,
+          I::Item: Display,
+          S: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut iter = self.iter.clone();
+        if let Some(first) = iter.next() {
+            try!(first.fmt(f));
+            for elt in iter {
+                try!(write!(f, "{}", self.sep));
+                try!(elt.fmt(f));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_join_display() {
+    let empty: [i32; 0] = [];
+    assert_eq!(join_display(&empty, ",").to_string(), "");
+    assert_eq!(join_display(Some("x"), ",").to_string(), "x");
+    let words = "a b c".split(' ');
+    let joined = join_display(words, " | ");
+    assert_eq!(joined.to_string(), "a | b | c");
+    assert_eq!(joined.to_string(), "a | b | c");
+    assert_eq!(format!("{:>3}", join_display(1..4, "")), "  1  2  3");
+}
+
+/// A writer into a byte buffer, for `write!` without allocation.
+///
+/// When a write doesn't fit, as much of it as fits without splitting a
+/// char is written, the writer remembers that the output was truncated,
+/// and the write returns an error.
+///
+/// ```
+/// use std::fmt::Write;
+/// use odds::fmt::FixedWriter;
+///
+/// let mut buf = [0; 16];
+/// let mut w = FixedWriter::new(&mut buf);
+/// write!(w, "temp={:.1}°C", 21.55).unwrap();
+/// assert_eq!(w.as_str(), "temp=21.6°C");
+///
+/// assert!(write!(w, " and rising").is_err());
+/// assert!(w.is_truncated());
+/// assert_eq!(w.into_str(), "temp=21.6°C and");
+/// ```
+pub struct FixedWriter<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    len: usize,
+    truncated: bool,
+}
+
+impl<'a> FixedWriter<'a> {
+    /// Create a writer that writes to the start of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        // only initialized bytes are ever written through the cast
+        let len = buf.len();
+        let buf = unsafe {
+            from_raw_parts_mut(buf.as_mut_ptr() as *mut MaybeUninit<u8>, len)
+        };
+        FixedWriter::from_uninit(buf)
+    }
+
+    /// Create a writer that writes to the start of the uninitialized `buf`.
+    pub fn from_uninit(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        FixedWriter {
+            buf: buf,
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    /// Return the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if nothing has been written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the size of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Return `true` if a write did not fit in the buffer.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Return the written part of the buffer.
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            str_from_written(&self.buf[..self.len])
+        }
+    }
+
+    /// Return the written part of the buffer, for the lifetime of the
+    /// buffer.
+    pub fn into_str(self) -> &'a str {
+        let buf: &'a [MaybeUninit<u8>] = self.buf;
+        unsafe {
+            str_from_written(&buf[..self.len])
+        }
+    }
+
+    /// Discard everything written, and the truncation flag.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+    }
+}
+
+/// The bytes of `buf` must be initialized, and valid UTF-8.
+unsafe fn str_from_written(buf: &[MaybeUninit<u8>]) -> &str {
+    str::from_utf8_unchecked(from_raw_parts(buf.as_ptr() as *const u8, buf.len()))
+}
+
+impl<'a> Write for FixedWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let room = self.buf.len() - self.len;
+        let (n, result) = if s.len() <= room {
+            (s.len(), Ok(()))
+        } else {
+            self.truncated = true;
+            (StrExt::floor_char_boundary(s, room), Err(fmt::Error))
+        };
+        for (dst, &b) in self.buf[self.len..self.len + n].iter_mut().zip(s.as_bytes()) {
+            *dst = MaybeUninit::new(b);
+        }
+        self.len += n;
+        result
+    }
+}
+
+impl<'a> fmt::Debug for FixedWriter<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("FixedWriter")
+         .field("written", &self.as_str())
+         .field("capacity", &self.capacity())
+         .field("truncated", &self.truncated)
+         .finish()
+    }
+}
+
+#[test]
+fn test_fixed_writer() {
+    let mut buf = [0; 6];
+    {
+        let mut w = FixedWriter::new(&mut buf);
+        assert_eq!(w.capacity(), 6);
+        assert!(w.is_empty());
+        w.write_str("ab").unwrap();
+        w.write_char('€').unwrap();
+        assert_eq!(w.len(), 5);
+        assert!(!w.is_empty());
+        // 'é' doesn't fit, and is not split
+        assert!(w.write_str("é").is_err());
+        assert_eq!(w.as_str(), "ab€");
+        assert!(w.is_truncated());
+        assert!(w.write_str("!!").is_err());
+        assert_eq!(w.as_str(), "ab€!");
+        w.clear();
+        assert!(w.is_empty());
+        assert!(!w.is_truncated());
+        write!(w, "{}", 123456).unwrap();
+        assert_eq!(w.into_str(), "123456");
+    }
+    assert_eq!(&buf, b"123456");
+
+    let mut uninit = [MaybeUninit::<u8>::uninit(); 4];
+    let mut w = FixedWriter::from_uninit(&mut uninit);
+    assert!(write!(w, "{:?}", "xyz").is_err());
+    assert_eq!(w.as_str(), "\"xyz");
+    assert_eq!(format!("{:?}", w),
+               r#"FixedWriter { written: "\"xyz", capacity: 4, truncated: true }"#);
+}
+
+/// Format `value` into `buf` as the shortest decimal that parses back to
+/// exactly `value`, and return the formatted part of it.
+///
+/// The digits come from libcore's float formatting (Grisu, with a Dragon4
+/// fallback for the rare values Grisu can't decide), so this works
+/// without `std`. They are laid out like JavaScript's `Number.toString`:
+/// in plain decimal notation for magnitudes from `1e-7` up to `1e21`, and
+/// with an exponent otherwise. Unlike JavaScript, negative zero keeps its
+/// sign. Infinities are written as `inf` and `-inf`, and NaN as `NaN`.
+///
+/// **Panics** if `buf` is too short; 25 bytes are always enough.
+///
+/// ```
+/// use odds::fmt::format_float;
+///
+/// let mut buf = [0; 25];
+/// assert_eq!(format_float(0.1 + 0.2, &mut buf), "0.30000000000000004");
+/// assert_eq!(format_float(-1.5e-9, &mut buf), "-1.5e-9");
+/// assert_eq!(format_float(6.02214076e23, &mut buf), "6.02214076e+23");
+/// assert_eq!(format_float(100.0, &mut buf), "100");
+/// ```
+pub fn format_float(value: f64, buf: &mut [u8]) -> &str {
+    // the shortest digits, as -d.ddde-x
+    let mut sci = [0; 32];
+    let mut w = FixedWriter::new(&mut sci);
+    write!(w, "{:e}", value).unwrap();
+    let sci = w.into_str();
+
+    let mut out = FixedWriter::new(buf);
+    let result = match sci.find('e') {
+        // inf or NaN
+        None => out.write_str(sci),
+        Some(e) => {
+            let negative = sci.starts_with('-');
+            let mantissa = &sci[negative as usize..e];
+            let mut digits = [0; 17];
+            let mut k = 0;
+            for &d in mantissa.as_bytes().iter().filter(|&&d| d != b'.') {
+                digits[k] = d;
+                k += 1;
+            }
+            let exp = ::num::parse_int::<i32>(sci[e + 1..].as_bytes()).unwrap();
+            write_decimal_layout(&mut out, negative, str_from_ascii(&digits[..k]), exp)
+        }
+    };
+    result.expect("format_float: buffer too short");
+    out.into_str()
+}
+
+/// Write the number `digits` × 10^(`exp` + 1 - `digits.len()`) like
+/// JavaScript's `Number.toString`.
+fn write_decimal_layout<W: Write>(out: &mut W, negative: bool, digits: &str, exp: i32)
+    -> fmt::Result
+{
+    let k = digits.len() as i32;
+    // the decimal point goes after the first n digits
+    let n = exp + 1;
+    if negative {
+        try!(out.write_str("-"));
+    }
+    if k <= n && n <= 21 {
+        try!(out.write_str(digits));
+        write_pad(out, '0', (n - k) as usize)
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        write!(out, "{}.{}", int, frac)
+    } else if -6 < n && n <= 0 {
+        try!(out.write_str("0."));
+        try!(write_pad(out, '0', -n as usize));
+        out.write_str(digits)
+    } else {
+        let (first, rest) = digits.split_at(1);
+        try!(out.write_str(first));
+        if !rest.is_empty() {
+            try!(write!(out, ".{}", rest));
+        }
+        write!(out, "e{}{}", if exp < 0 { '-' } else { '+' }, exp.abs())
+    }
+}
+
+fn str_from_ascii(bytes: &[u8]) -> &str {
+    str::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn test_format_float() {
+    use std::f64;
+    use random::{RandomSource, XorShift};
+
+    let mut buf = [0; 25];
+    let cases = [
+        (0.0, "0"), (-0.0, "-0"), (1.0, "1"), (-2.5, "-2.5"), (123456.789, "123456.789"),
+        (1e20, "100000000000000000000"), (1e21, "1e+21"), (1.5e21, "1.5e+21"),
+        (1e-6, "0.000001"), (1.25e-6, "0.00000125"), (1e-7, "1e-7"), (0.1, "0.1"),
+        (f64::MAX, "1.7976931348623157e+308"), (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        (5e-324, "5e-324"), (-1.234e-7, "-1.234e-7"), (-0.000001234, "-0.000001234"),
+        (f64::INFINITY, "inf"), (f64::NEG_INFINITY, "-inf"), (f64::NAN, "NaN"),
+    ];
+    for &(value, expected) in &cases {
+        assert_eq!(format_float(value, &mut buf), expected);
+    }
+
+    // the significant digits, without leading or trailing zeros
+    fn significant(s: &str) -> String {
+        let mantissa = s.trim_start_matches('-').split('e').next().unwrap();
+        mantissa.replace('.', "").trim_matches('0').to_string()
+    }
+    // long digit strings around the switches between notations
+    let values = (-9..24).map(|e| -10f64.powi(e) / 3.0)
+                         .chain((-9..24).map(|e| 10f64.powi(e) * 0.7));
+    let mut rng = XorShift::new(5);
+    for value in values.chain((0..10000).map(|_| f64::from_bits(rng.next_u64()))) {
+        let s = format_float(value, &mut buf);
+        if !value.is_nan() {
+            assert_eq!(s.parse::<f64>().unwrap().to_bits(), value.to_bits(), "{}", s);
+            assert_eq!(significant(s), significant(&format!("{:e}", value)));
+        }
+    }
+}
+
+#[test]
+fn test_format_int() {
+    macro_rules! check {
+        ($($t:ty)+) => {
+            $(
+            let mut buf = [0xff; 40];
+            let min = <$t>::min_value();
+            let max = <$t>::max_value();
+            for &v in &[min, min + 1, max - 1, max, 0, 1, 9, 10, 99, 100, 101, 127] {
+                let v = v as $t;
+                assert_eq!(format_int(v, &mut buf), &*v.to_string());
+                assert!(v.to_string().len() <= <$t>::MAX_LEN);
+            }
+            assert_eq!(max.to_string().len().max(min.to_string().len()), <$t>::MAX_LEN);
+            )+
+        }
+    }
+    check!{u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize}
+
+    let mut buf = [0; 40];
+    let mut x = Some(1u128);
+    while let Some(p) = x {
+        assert_eq!(format_int(p, &mut buf), &*p.to_string());
+        assert_eq!(format_int(p - 1, &mut buf), &*(p - 1).to_string());
+        x = p.checked_mul(10);
+    }
+    assert_eq!(format_int(10u128.pow(19) * 5 + 7, &mut buf), "50000000000000000007");
+}
+
+#[test]
+fn test_escape() {
+    for &s in &["", "plain", "\t\r\n\\'\"", "αβγ\u{0}\u{7f}\u{301}x\u{200b}", "€𝄞"] {
+        assert_eq!(escape_default(s).to_string(), s.chars().flat_map(char::escape_default)
+                                                            .collect::<String>());
+        assert_eq!(escape_debug(s).to_string(), s.chars().flat_map(char::escape_debug)
+                                                          .collect::<String>());
+    }
+    assert_eq!(escape_default(&b"\xe2\x82\xe2\x82\xac"[..]).to_string(), "\\xe2\\x82\\u{20ac}");
+    assert_eq!(escape_debug(&[0x80u8, b'"']).to_string(), "\\x80\\\"");
+}
+
+#[test]
+fn test_fixed_width() {
+    let mut s = String::new();
+    format_fixed_width("αβ", 3, '-', &mut s).unwrap();
+    assert_eq!(s, "-αβ");
+    s.clear();
+    format_fixed_width(-1.5, 4, ' ', &mut s).unwrap();
+    assert_eq!(s, "-1.5");
+    s.clear();
+    assert_eq!(format_fixed_width_left(100, 2, ' ', &mut s), Err(Overflow(())));
+    assert_eq!(format_fixed_width(0, 0, ' ', &mut s), Err(Overflow(())));
+    assert_eq!(format_fixed_width("", 0, ' ', &mut s), Ok(()));
+    assert_eq!(s, "");
+    format_fixed_width_or_fill(-10, 2, ' ', '*', &mut s).unwrap();
+    assert_eq!(s, "**");
+}
@@ -0,0 +1,240 @@
+//! Iterator extensions
+
+use std::slice::Iter;
+
+use slice::iter::{SliceCopyIter, SliceIter};
+
+/// The result of a step of `.fold_while()`: whether to continue or stop.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FoldWhile<B> {
+    /// Continue folding with this accumulator
+    Continue(B),
+    /// Stop folding, with this result
+    Done(B),
+}
+
+impl<B> FoldWhile<B> {
+    /// Return the accumulator or result.
+    pub fn into_inner(self) -> B {
+        match self {
+            FoldWhile::Continue(x) | FoldWhile::Done(x) => x,
+        }
+    }
+
+    /// Return `true` if the fold stopped early.
+    pub fn is_done(&self) -> bool {
+        match *self {
+            FoldWhile::Continue(_) => false,
+            FoldWhile::Done(_) => true,
+        }
+    }
+}
+
+/// Extra methods for the slice iterators, implemented with unrolled loops.
+pub trait IterExt : Iterator {
+    /// Fold the elements with `f`, starting from `init`, until `f` returns
+    /// `FoldWhile::Done`.
+    ///
+    /// Return `Done` with the result if the fold stopped early, otherwise
+    /// `Continue` with the final accumulator. The iterator is left after the
+    /// last element that was folded.
+    ///
+    /// ```
+    /// use odds::iter::{FoldWhile, IterExt};
+    ///
+    /// let data = [1., 2., 4., 8., 16.];
+    /// let mut iter = data.iter();
+    /// // sum elements until the sum exceeds a limit
+    /// let res = iter.fold_while(0., |acc, &x| {
+    ///     let sum = acc + x;
+    ///     if sum > 5. { FoldWhile::Done(acc) } else { FoldWhile::Continue(sum) }
+    /// });
+    /// assert_eq!(res, FoldWhile::Done(3.));
+    /// assert_eq!(iter.as_slice(), &[8., 16.]);
+    /// ```
+    fn fold_while<B, F>(&mut self, init: B, f: F) -> FoldWhile<B>
+        where F: FnMut(B, Self::Item) -> FoldWhile<B>;
+
+    /// Return the minimum and maximum elements, in one pass using about 1.5
+    /// comparisons per element; or `None` if the iterator is empty.
+    ///
+    /// If several elements are equally minimum, the first is returned; if
+    /// several are equally maximum, the last is returned.
+    ///
+    /// ```
+    /// use odds::iter::IterExt;
+    ///
+    /// let data = [3, 1, 4, 1, 5, 9, 2, 6];
+    /// assert_eq!(data.iter().minmax(), Some((&1, &9)));
+    /// assert_eq!([0; 0].iter().minmax(), None);
+    /// ```
+    fn minmax(self) -> Option<(Self::Item, Self::Item)>
+        where Self: Sized,
+              Self::Item: Ord + Clone
+    {
+        minmax_impl(self, |a, b| a < b)
+    }
+
+    /// Return the elements with the minimum and maximum keys, in one pass;
+    /// or `None` if the iterator is empty.
+    ///
+    /// The key function is called once for each element. Ties are resolved
+    /// like in `.minmax()`.
+    ///
+    /// ```
+    /// use odds::iter::IterExt;
+    ///
+    /// let words = ["bb", "a", "ccc", "dd", "eee"];
+    /// assert_eq!(words.iter().minmax_by_key(|s| s.len()), Some((&"a", &"eee")));
+    /// ```
+    fn minmax_by_key<K, F>(self, mut key: F) -> Option<(Self::Item, Self::Item)>
+        where Self: Sized,
+              Self::Item: Clone,
+              K: Ord + Clone,
+              F: FnMut(&Self::Item) -> K
+    {
+        let keyed = self.map(|x| (key(&x), x));
+        minmax_impl(keyed, |a, b| a.0 < b.0).map(|(min, max)| (min.1, max.1))
+    }
+}
+
+/// Return the minimum and maximum elements by `less_than`, comparing the
+/// elements in pairs.
+fn minmax_impl<I, F>(mut iter: I, mut less_than: F) -> Option<(I::Item, I::Item)>
+    where I: Iterator,
+          I::Item: Clone,
+          F: FnMut(&I::Item, &I::Item) -> bool
+{
+    let (mut min, mut max) = match iter.next() {
+        None => return None,
+        Some(x) => (x.clone(), x),
+    };
+    loop {
+        let first = match iter.next() {
+            None => break,
+            Some(x) => x,
+        };
+        let (lo, hi) = match iter.next() {
+            None => (first.clone(), first),
+            Some(second) => {
+                if less_than(&second, &first) {
+                    (second, first)
+                } else {
+                    (first, second)
+                }
+            }
+        };
+        if less_than(&lo, &min) {
+            min = lo;
+        }
+        if !less_than(&hi, &max) {
+            max = hi;
+        }
+    }
+    Some((min, max))
+}
+
+/// Fold the elements of `data` until `f` returns `Done`. Return the number
+/// of elements folded, and the result.
+#[inline]
+fn fold_while_slice<'a, T, B, F>(data: &'a [T], init: B, mut f: F) -> (usize, FoldWhile<B>)
+    where F: FnMut(B, &'a T) -> FoldWhile<B>
+{
+    let mut acc = init;
+    let mut tail = data;
+    macro_rules! step {
+        ($i:expr) => {
+            match f(acc, &tail[$i]) {
+                FoldWhile::Continue(x) => acc = x,
+                FoldWhile::Done(x) => {
+                    return (data.len() - tail.len() + $i + 1, FoldWhile::Done(x));
+                }
+            }
+        }
+    }
+    while tail.len() >= 4 {
+        step!(0);
+        step!(1);
+        step!(2);
+        step!(3);
+        tail = &tail[4..];
+    }
+    while tail.len() > 0 {
+        step!(0);
+        tail = &tail[1..];
+    }
+    (data.len(), FoldWhile::Continue(acc))
+}
+
+impl<'a, T> IterExt for Iter<'a, T> {
+    fn fold_while<B, F>(&mut self, init: B, f: F) -> FoldWhile<B>
+        where F: FnMut(B, &'a T) -> FoldWhile<B>
+    {
+        let data = self.as_slice();
+        let (n, res) = fold_while_slice(data, init, f);
+        *self = data[n..].iter();
+        res
+    }
+}
+
+impl<'a, T> IterExt for SliceIter<'a, T> {
+    fn fold_while<B, F>(&mut self, init: B, f: F) -> FoldWhile<B>
+        where F: FnMut(B, &'a T) -> FoldWhile<B>
+    {
+        let data = self.as_slice();
+        let (n, res) = fold_while_slice(data, init, f);
+        *self = SliceIter::from(&data[n..]);
+        res
+    }
+}
+
+impl<'a, T> IterExt for SliceCopyIter<'a, T>
+    where T: Copy
+{
+    fn fold_while<B, F>(&mut self, init: B, mut f: F) -> FoldWhile<B>
+        where F: FnMut(B, T) -> FoldWhile<B>
+    {
+        let data = self.as_slice();
+        let (n, res) = fold_while_slice(data, init, |acc, &x| f(acc, x));
+        *self = SliceCopyIter::from(&data[n..]);
+        res
+    }
+}
+
+#[test]
+fn test_fold_while() {
+    let data = (0..20).collect::<Vec<u32>>();
+    for stop in 0..22 {
+        let mut iter = SliceCopyIter::from(&data[..]);
+        let res = iter.fold_while(0, |acc, x| {
+            if x == stop { FoldWhile::Done(acc) } else { FoldWhile::Continue(acc + x) }
+        });
+        if stop < 20 {
+            assert_eq!(res, FoldWhile::Done(stop * (stop.max(1) - 1) / 2));
+            assert_eq!(iter.as_slice(), &data[stop as usize + 1..]);
+        } else {
+            assert_eq!(res, FoldWhile::Continue(190));
+            assert_eq!(iter.len(), 0);
+        }
+    }
+
+    let mut iter = SliceIter::from(&data[..]);
+    let res = iter.fold_while(0, |acc, _| FoldWhile::Continue(acc + 1));
+    assert!(!res.is_done());
+    assert_eq!(res.into_inner(), 20);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_minmax() {
+    let data = [(1, 'a'), (0, 'b'), (2, 'c'), (0, 'd'), (2, 'e'), (1, 'f')];
+    for len in 1..data.len() + 1 {
+        let iter = SliceCopyIter::from(&data[..len]);
+        let (min, max) = iter.minmax_by_key(|x| x.0).unwrap();
+        assert_eq!(Some(min), data[..len].iter().cloned().min_by_key(|x| x.0));
+        assert_eq!(Some(max), data[..len].iter().cloned().max_by_key(|x| x.0));
+        assert_eq!(iter.minmax(), Some((*data[..len].iter().min().unwrap(),
+                                        *data[..len].iter().max().unwrap())));
+    }
+    assert_eq!(SliceIter::from(&data[..0]).minmax(), None);
+}
@@ -3,6 +3,14 @@
 //! - Utilities for debug-checked, release-unchecked indexing and slicing
 //! - Fixpoint combinator for closures
 //! - String and Vec extensions
+//! - Formatting helpers that don't allocate
+//! - One-time initialization without poisoning
+//! - Raw pointer extensions
+//! - Slice shuffling with a small built-in random number generator
+//! - Incremental UTF-8 validation
+//! - UTF-16 conversion into caller-provided buffers
+//! - Edit distances between strings and slices
+//! - Integer parsing from bytes
 //!
 //! The **odds** crate has the following crate feature flags:
 //!
@@ -14,26 +22,37 @@
 //!   - Optional.
 //!   - Requires nightly channel.
 //!   - Implement the closure traits for **Fix**.
+//!   - Use `slice::find_byte` for `SliceFind` on byte slices.
 //!
 
 #![doc(html_root_url = "https://docs.rs/odds/0.2/")]
-#![cfg_attr(feature="unstable", feature(unboxed_closures, fn_traits))]
+#![cfg_attr(feature="unstable", feature(unboxed_closures, fn_traits, min_specialization))]
 
 #![cfg_attr(not(feature="std"), no_std)]
 
 #[cfg(not(feature="std"))]
 extern crate core as std;
 extern crate rawslice;
-extern crate rawpointer;
 
-mod range;
+pub mod range;
 #[path = "fix.rs"]
 mod fix_impl;
 pub mod char;
+#[cfg(feature="std")]
+pub mod distance;
+pub mod fmt;
+pub mod iter;
+pub mod num;
+pub mod pointer;
+pub mod random;
 pub mod string;
 pub mod vec;
 pub mod slice;
 pub mod stride;
+#[cfg(target_has_atomic = "ptr")]
+pub mod sync;
+pub mod utf16;
+pub mod utf8;
 
 pub use fix_impl::Fix;
 pub use fix_impl::fix;
@@ -41,12 +60,14 @@ pub use range::IndexRange;
 
 use std::mem;
 
+use pointer::PointerExt;
+
 /// prelude of often used traits and functions
 pub mod prelude {
+    pub use slice::SliceExt;
     pub use slice::SliceFind;
     pub use slice::SliceIterExt;
     pub use string::StrExt;
-    #[cfg(feature="std")]
     pub use string::StrChunksWindows;
     #[cfg(feature="std")]
     pub use string::StringExt;
@@ -115,7 +136,7 @@ pub unsafe fn debug_assert_unreachable() -> ! {
 #[inline]
 pub unsafe fn slice_unchecked<T>(data: &[T], from: usize, to: usize) -> &[T] {
     debug_assert!((&data[from..to], true).1);
-    std::slice::from_raw_parts(data.as_ptr().offset(from as isize), to - from)
+    std::slice::from_raw_parts(data.as_ptr().debug_add(from), to - from)
 }
 
 /// Check slicing bounds in debug mode, otherwise just act as an unchecked
@@ -123,7 +144,7 @@ pub unsafe fn slice_unchecked<T>(data: &[T], from: usize, to: usize) -> &[T] {
 #[inline]
 pub unsafe fn slice_unchecked_mut<T>(data: &mut [T], from: usize, to: usize) -> &mut [T] {
     debug_assert!((&data[from..to], true).1);
-    std::slice::from_raw_parts_mut(data.as_mut_ptr().offset(from as isize), to - from)
+    std::slice::from_raw_parts_mut(data.as_mut_ptr().debug_add(from), to - from)
 }
 
 /// Create a length 1 slice out of a reference
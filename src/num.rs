@@ -0,0 +1,245 @@
+//! Integer parsing from bytes
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// The kind of error from parsing an integer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IntErrorKind {
+    /// The input was empty.
+    Empty,
+    /// The input contained a byte that is not a digit, or was only a sign.
+    InvalidDigit,
+    /// The value is too large or too small for the integer type.
+    Overflow,
+}
+
+/// Error returned when parsing an integer fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseIntError {
+    kind: IntErrorKind,
+}
+
+impl Display for ParseIntError {
+    #[inline]
+    fn fmt(&self, fmtr: &mut Formatter) -> fmt::Result {
+        fmtr.pad(self.description())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseIntError {
+    #[inline]
+    fn description(&self) -> &str {
+        ParseIntError::description(self)
+    }
+}
+
+impl ParseIntError {
+    /// Return the kind of error.
+    #[inline]
+    pub fn kind(&self) -> IntErrorKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn description(&self) -> &str {
+        match self.kind {
+            IntErrorKind::Empty => "cannot parse integer from empty input",
+            IntErrorKind::InvalidDigit => "invalid digit found in input",
+            IntErrorKind::Overflow => "number too large or too small to fit in target type",
+        }
+    }
+}
+
+/// Primitive integer types, for `parse_int` and `parse_int_partial`.
+pub trait ParseInt : Copy {
+    #[doc(hidden)]
+    const SIGNED: bool;
+    #[doc(hidden)]
+    fn from_magnitude(m: u64, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_parse_int {
+    (@signed $($t:ty)+) => {
+        $(
+        impl ParseInt for $t {
+            const SIGNED: bool = true;
+            #[inline(always)]
+            fn from_magnitude(m: u64, negative: bool) -> Option<Self> {
+                if !negative && m <= <$t>::max_value() as u64 {
+                    Some(m as $t)
+                } else if negative && m <= <$t>::max_value() as u64 + 1 {
+                    // the minimum wraps to itself
+                    Some((m as $t).wrapping_neg())
+                } else {
+                    None
+                }
+            }
+        }
+        )+
+    };
+    ($($t:ty)+) => {
+        $(
+        impl ParseInt for $t {
+            const SIGNED: bool = false;
+            #[inline(always)]
+            fn from_magnitude(m: u64, _negative: bool) -> Option<Self> {
+                if m <= <$t>::max_value() as u64 { Some(m as $t) } else { None }
+            }
+        }
+        )+
+    };
+}
+impl_parse_int!{u8 u16 u32 u64 usize}
+impl_parse_int!{@signed i8 i16 i32 i64 isize}
+
+/// Parse the decimal integer that makes up all of `data`.
+///
+/// The number may start with `+`, or with `-` for signed types. Unlike
+/// `str::parse`, this works on bytes directly, so there is no UTF-8
+/// validation; for a `&str`, pass `s.as_bytes()`. Digits are converted
+/// eight at a time.
+///
+/// ```
+/// use odds::num::{parse_int, IntErrorKind};
+///
+/// assert_eq!(parse_int::<i32>(b"-1234"), Ok(-1234));
+/// assert_eq!(parse_int::<u64>("18446744073709551615".as_bytes()), Ok(!0));
+/// assert_eq!(parse_int::<u8>(b"256").unwrap_err().kind(), IntErrorKind::Overflow);
+/// assert_eq!(parse_int::<u8>(b"1,5").unwrap_err().kind(), IntErrorKind::InvalidDigit);
+/// ```
+pub fn parse_int<T>(data: &[u8]) -> Result<T, ParseIntError>
+    where T: ParseInt
+{
+    match parse_int_partial(data) {
+        Ok((value, len)) if len == data.len() => Ok(value),
+        Ok(_) => Err(ParseIntError { kind: IntErrorKind::InvalidDigit }),
+        Err(e) => Err(e),
+    }
+}
+
+/// Parse the decimal integer at the start of `data`, and return it along
+/// with the number of bytes it took up.
+///
+/// Parsing stops at the first byte that is not a digit. It is an error if
+/// there are no digits, or if the value doesn't fit in `T`.
+///
+/// ```
+/// use odds::num::parse_int_partial;
+///
+/// let row = b"42,-7,x";
+/// let (a, n) = parse_int_partial::<i16>(row).unwrap();
+/// let (b, m) = parse_int_partial::<i16>(&row[n + 1..]).unwrap();
+/// assert_eq!((a, b), (42, -7));
+/// assert!(parse_int_partial::<i16>(&row[n + m + 2..]).is_err());
+/// ```
+pub fn parse_int_partial<T>(data: &[u8]) -> Result<(T, usize), ParseIntError>
+    where T: ParseInt
+{
+    let (negative, sign_len) = match data.first() {
+        None => return Err(ParseIntError { kind: IntErrorKind::Empty }),
+        Some(&b'-') if T::SIGNED => (true, 1),
+        Some(&b'+') => (false, 1),
+        Some(_) => (false, 0),
+    };
+    let (magnitude, digits) = parse_digits(&data[sign_len..]);
+    if digits == 0 {
+        return Err(ParseIntError { kind: IntErrorKind::InvalidDigit });
+    }
+    match magnitude.and_then(|m| T::from_magnitude(m, negative)) {
+        Some(value) => Ok((value, sign_len + digits)),
+        None => Err(ParseIntError { kind: IntErrorKind::Overflow }),
+    }
+}
+
+/// Return the value of the digits at the start of `data`, or `None` if it
+/// overflows, and the number of digits.
+#[inline]
+fn parse_digits(data: &[u8]) -> (Option<u64>, usize) {
+    let mut acc = Some(0u64);
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        let mut word = [0; 8];
+        word.copy_from_slice(&data[i..i + 8]);
+        match parse_8_digits(u64::from_le_bytes(word)) {
+            Some(value) => {
+                acc = acc.and_then(|a| a.checked_mul(100_000_000))
+                         .and_then(|a| a.checked_add(value));
+                i += 8;
+            }
+            None => break,
+        }
+    }
+    while let Some(&b) = data.get(i) {
+        if !b.is_ascii_digit() {
+            break;
+        }
+        acc = acc.and_then(|a| a.checked_mul(10))
+                 .and_then(|a| a.checked_add((b - b'0') as u64));
+        i += 1;
+    }
+    (acc, i)
+}
+
+/// Return the value of eight ASCII digits, read as a little endian word, or
+/// `None` if any of the bytes is not a digit.
+#[inline]
+fn parse_8_digits(word: u64) -> Option<u64> {
+    const LO: u64 = 0x0101010101010101;
+    // digits are 0x30 to 0x39: the high nibble is 3, and adding 6 to the
+    // low nibble doesn't carry
+    let high_nibbles = word & (0xF0 * LO);
+    let carried = word.wrapping_add(0x06 * LO) & (0xF0 * LO);
+    if high_nibbles != 0x30 * LO || carried != 0x30 * LO {
+        return None;
+    }
+    // combine adjacent digits into pairs, pairs into groups of four, and
+    // those into the final value; the first digit is in the lowest byte
+    let mut x = word - 0x30 * LO;
+    x = (x * 10 + (x >> 8)) & 0x00FF00FF00FF00FF;
+    x = (x * 100 + (x >> 16)) & 0x0000FFFF0000FFFF;
+    x = (x * 10000 + (x >> 32)) & 0xFFFFFFFF;
+    Some(x)
+}
+
+#[test]
+fn test_parse_int() {
+    use std::str::FromStr;
+    use std::fmt::Debug;
+
+    fn check<T>(s: &str)
+        where T: ParseInt + FromStr + PartialEq + Debug
+    {
+        let expected = T::from_str(s).ok();
+        assert_eq!(parse_int::<T>(s.as_bytes()).ok(), expected, "{:?}", s);
+    }
+
+    let inputs = ["", "0", "+0", "-0", "+", "-", "7", "-128", "128", "-129", "255", "256",
+                  "12345678", "123456789", "1234567x", "1234567890123456789",
+                  "-9223372036854775808", "-9223372036854775809", "9223372036854775807",
+                  "18446744073709551615", "18446744073709551616", "00000000000000000000042",
+                  "99999999999999999999", "1 ", " 1", "+-1", "12345678/", "12345678:"];
+    for &s in &inputs {
+        check::<u8>(s);
+        check::<i8>(s);
+        check::<u16>(s);
+        check::<i32>(s);
+        check::<u64>(s);
+        check::<i64>(s);
+        check::<usize>(s);
+    }
+    for digit in 0..256 {
+        let mut s = *b"11111111";
+        for i in 0..8 {
+            s[i] = digit as u8;
+            let expected = ::std::str::from_utf8(&s).ok().and_then(|s| s.parse().ok());
+            assert_eq!(parse_int::<u32>(&s).ok(), expected);
+            s[i] = b'1';
+        }
+    }
+    assert_eq!(parse_int::<i32>(b"").unwrap_err().kind(), IntErrorKind::Empty);
+    assert_eq!(parse_int_partial::<u32>(b"123456789abc"), Ok((123456789, 9)));
+    assert_eq!(parse_int_partial::<u32>(b"-1").unwrap_err().kind(), IntErrorKind::InvalidDigit);
+}
@@ -0,0 +1,442 @@
+//! Raw pointer extensions
+
+use std::mem::size_of;
+use std::ptr;
+use std::slice;
+
+/// Extension methods for raw pointers.
+///
+/// Implemented for `*const T` and `*mut T`.
+pub trait PointerExt : Copy {
+    /// Offset the pointer by `i` elements.
+    ///
+    /// Same safety requirements as the raw pointer `offset` method.
+    unsafe fn offset(self, i: isize) -> Self;
+
+    /// Increment the pointer by `count` elements.
+    ///
+    /// Same safety requirements as `offset`. In debug builds, assert that
+    /// `count` elements fit in an `isize` offset.
+    #[inline(always)]
+    unsafe fn debug_add(self, count: usize) -> Self {
+        debug_assert!(self.fits_offset(count), "debug_add: offset overflows isize");
+        self.offset(count as isize)
+    }
+
+    /// Decrement the pointer by `count` elements.
+    ///
+    /// Same safety requirements as `offset`. In debug builds, assert that
+    /// `count` elements fit in an `isize` offset.
+    #[inline(always)]
+    unsafe fn debug_sub(self, count: usize) -> Self {
+        debug_assert!(self.fits_offset(count), "debug_sub: offset overflows isize");
+        self.offset((count as isize).wrapping_neg())
+    }
+
+    #[doc(hidden)]
+    fn fits_offset(self, count: usize) -> bool;
+
+    /// Increment the pointer by one element, and return its old value.
+    ///
+    /// Equivalent to the C idiom `ptr++`.
+    #[inline(always)]
+    unsafe fn post_increment(&mut self) -> Self {
+        let current = *self;
+        *self = self.offset(1);
+        current
+    }
+
+    /// Increment the pointer by one element, and return its new value.
+    ///
+    /// Equivalent to the C idiom `++ptr`.
+    #[inline(always)]
+    unsafe fn pre_increment(&mut self) -> Self {
+        *self = self.offset(1);
+        *self
+    }
+
+    /// Decrement the pointer by one element, and return its old value.
+    ///
+    /// Equivalent to the C idiom `ptr--`.
+    #[inline(always)]
+    unsafe fn post_decrement(&mut self) -> Self {
+        let current = *self;
+        *self = self.offset(-1);
+        current
+    }
+
+    /// Return the number of elements from `self` to `end`.
+    ///
+    /// Return the distance in bytes if the element type is zero-sized.
+    ///
+    /// `end` must not be before `self`; this is checked with a debug
+    /// assertion.
+    ///
+    /// ```
+    /// use odds::pointer::PointerExt;
+    ///
+    /// let data = [1u16, 2, 3, 4];
+    /// let ptr = data.as_ptr();
+    /// let end = unsafe { ptr.debug_add(data.len()) };
+    /// assert_eq!(ptr.distance_to(end), 4);
+    /// ```
+    fn distance_to(self, end: Self) -> usize;
+
+    /// Offset the pointer by `i` elements, wrapping around the address
+    /// space on overflow.
+    ///
+    /// This is safe, but the resulting pointer may only be dereferenced if
+    /// it is in bounds of the allocation it started out in.
+    fn wrapping_offset(self, i: isize) -> Self;
+
+    /// Increment the pointer by one element, wrapping around on overflow.
+    #[inline(always)]
+    fn wrapping_inc(&mut self) {
+        *self = self.wrapping_offset(1);
+    }
+
+    /// Decrement the pointer by one element, wrapping around on overflow.
+    ///
+    /// ```
+    /// use odds::pointer::PointerExt;
+    ///
+    /// let data = [1, 2, 3];
+    /// // an end pointer for walking backwards, before the start
+    /// let mut stop = data.as_ptr();
+    /// stop.wrapping_dec();
+    /// let mut ptr = data.as_ptr().wrapping_offset(2);
+    /// let mut rev = Vec::new();
+    /// while ptr != stop {
+    ///     rev.push(unsafe { *ptr });
+    ///     ptr.wrapping_dec();
+    /// }
+    /// assert_eq!(rev, [3, 2, 1]);
+    /// ```
+    #[inline(always)]
+    fn wrapping_dec(&mut self) {
+        *self = self.wrapping_offset(-1);
+    }
+
+    /// Round the address up to the next multiple of `align` bytes.
+    ///
+    /// **Panics** if `align` is not a power of two.
+    ///
+    /// ```
+    /// use odds::pointer::PointerExt;
+    ///
+    /// let p = 13 as *const u8;
+    /// assert_eq!(p.align_up(8) as usize, 16);
+    /// assert_eq!(p.align_down(8) as usize, 8);
+    /// assert!(p.align_up(8).is_aligned_to(8));
+    /// ```
+    fn align_up(self, align: usize) -> Self;
+
+    /// Round the address down to the previous multiple of `align` bytes.
+    ///
+    /// **Panics** if `align` is not a power of two.
+    fn align_down(self, align: usize) -> Self;
+
+    /// Return `true` if the address is a multiple of `align` bytes.
+    ///
+    /// **Panics** if `align` is not a power of two.
+    fn is_aligned_to(self, align: usize) -> bool;
+
+    /// Offset the pointer by `n` bytes.
+    ///
+    /// Same safety requirements as `offset`, but counted in bytes.
+    ///
+    /// ```
+    /// use odds::pointer::PointerExt;
+    ///
+    /// // a header of a u16 tag followed by a u32 length, little endian
+    /// let header = [1u8, 0, 5, 0, 0, 0];
+    /// let ptr = header.as_ptr();
+    /// let len = unsafe { (ptr.add_bytes(2) as *const u32).read_unaligned() };
+    /// assert_eq!(u32::from_le(len), 5);
+    /// ```
+    unsafe fn offset_bytes(self, n: isize) -> Self;
+
+    /// Increment the pointer by `n` bytes.
+    #[inline(always)]
+    unsafe fn add_bytes(self, n: usize) -> Self {
+        self.offset_bytes(n as isize)
+    }
+
+    /// Decrement the pointer by `n` bytes.
+    #[inline(always)]
+    unsafe fn sub_bytes(self, n: usize) -> Self {
+        self.offset_bytes((n as isize).wrapping_neg())
+    }
+
+    /// Return `true` if the pointer is in the range `[start, end)`.
+    ///
+    /// All three pointers should be in (or one past the end of) the same
+    /// allocation. `start` must not be after `end`; this is checked with a
+    /// debug assertion.
+    ///
+    /// ```
+    /// use odds::pointer::PointerExt;
+    ///
+    /// let data = [1, 2, 3];
+    /// let start = data.as_ptr();
+    /// let end = start.wrapping_offset(3);
+    /// assert!(data[1..].as_ptr().in_range(start, end));
+    /// assert!(!end.in_range(start, end));
+    /// ```
+    fn in_range(self, start: Self, end: Self) -> bool;
+
+    /// Return the lower of the two pointers.
+    fn min_ptr(self, other: Self) -> Self;
+
+    /// Return the higher of the two pointers.
+    fn max_ptr(self, other: Self) -> Self;
+}
+
+/// Extension methods for mutable raw pointers.
+///
+/// Implemented for `*mut T`.
+///
+/// ```
+/// use odds::pointer::PointerExt;
+///
+/// let mut data = [1, 2, 3];
+/// let mut ptr = data.as_mut_ptr();
+/// unsafe {
+///     let end = ptr.offset(3);
+///     while ptr != end {
+///         let x = ptr.read();
+///         ptr.post_increment().write(x * 10);
+///     }
+/// }
+/// assert_eq!(data, [10, 20, 30]);
+/// ```
+pub trait PointerMutExt : PointerExt {
+    /// Drop the `count` elements starting at the pointer, in place.
+    ///
+    /// If one of the destructors panics, the rest of the elements are still
+    /// dropped. Same safety requirements as `std::ptr::drop_in_place`.
+    ///
+    /// ```
+    /// use odds::pointer::{PointerExt, PointerMutExt};
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// // remove the first element of a vector by hand
+    /// let mut v = ManuallyDrop::new(vec![String::from("a"), String::from("b")]);
+    /// let ptr = v.as_mut_ptr();
+    /// unsafe {
+    ///     ptr.drop_in_place_n(1);
+    ///     ptr.offset(1).copy_to(ptr, 1);
+    ///     v.set_len(1);
+    /// }
+    /// assert_eq!(*v, [String::from("b")]);
+    /// # unsafe { ManuallyDrop::drop(&mut v) }
+    /// ```
+    unsafe fn drop_in_place_n(self, count: usize);
+}
+
+impl<T> PointerMutExt for *mut T {
+    #[inline]
+    unsafe fn drop_in_place_n(self, count: usize) {
+        ptr::drop_in_place(slice::from_raw_parts_mut(self, count))
+    }
+}
+
+macro_rules! impl_pointer_ext {
+    ($ptr:ty, $byte_ptr:ty) => {
+        impl<T> PointerExt for $ptr {
+            #[inline(always)]
+            unsafe fn offset(self, i: isize) -> Self {
+                self.offset(i)
+            }
+
+            #[inline(always)]
+            fn fits_offset(self, count: usize) -> bool {
+                match count.checked_mul(size_of::<T>()) {
+                    Some(bytes) => bytes <= isize::max_value() as usize,
+                    None => false,
+                }
+            }
+
+            #[inline(always)]
+            unsafe fn offset_bytes(self, n: isize) -> Self {
+                (self as $byte_ptr).offset(n) as Self
+            }
+
+            #[inline]
+            fn in_range(self, start: Self, end: Self) -> bool {
+                debug_assert!(start <= end, "in_range: start is after end");
+                start <= self && self < end
+            }
+
+            #[inline]
+            fn min_ptr(self, other: Self) -> Self {
+                if other < self { other } else { self }
+            }
+
+            #[inline]
+            fn max_ptr(self, other: Self) -> Self {
+                if other < self { self } else { other }
+            }
+
+            #[inline(always)]
+            fn wrapping_offset(self, i: isize) -> Self {
+                self.wrapping_offset(i)
+            }
+
+            #[inline]
+            fn align_up(self, align: usize) -> Self {
+                assert!(align.is_power_of_two());
+                let addr = self as usize;
+                (addr.wrapping_add(align - 1) & !(align - 1)) as Self
+            }
+
+            #[inline]
+            fn align_down(self, align: usize) -> Self {
+                assert!(align.is_power_of_two());
+                (self as usize & !(align - 1)) as Self
+            }
+
+            #[inline]
+            fn is_aligned_to(self, align: usize) -> bool {
+                assert!(align.is_power_of_two());
+                self as usize & (align - 1) == 0
+            }
+
+            #[inline(always)]
+            fn distance_to(self, end: Self) -> usize {
+                debug_assert!(self as usize <= end as usize || size_of::<T>() == 0,
+                              "distance_to: end is before start");
+                let diff = (end as usize).wrapping_sub(self as usize);
+                let size = size_of::<T>();
+                if size == 0 {
+                    diff
+                } else {
+                    diff / size
+                }
+            }
+        }
+    }
+}
+
+impl_pointer_ext!(*const T, *const u8);
+impl_pointer_ext!(*mut T, *mut u8);
+
+#[test]
+fn test_distance_to() {
+    let mut data = [0u32; 5];
+    let ptr = data.as_mut_ptr();
+    unsafe {
+        let end = PointerExt::offset(ptr, 5);
+        assert_eq!(ptr.distance_to(end), 5);
+        assert_eq!(end.distance_to(end), 0);
+
+        let mut cursor = ptr as *const u32;
+        assert_eq!(cursor.post_increment(), ptr as *const u32);
+        assert_eq!(cursor.post_increment(), ptr.offset(1) as *const u32);
+        assert_eq!(cursor, ptr.offset(2) as *const u32);
+        assert_eq!(cursor.pre_increment(), ptr.offset(3) as *const u32);
+        assert_eq!(cursor.post_decrement(), ptr.offset(3) as *const u32);
+        assert_eq!(cursor, ptr.offset(2) as *const u32);
+    }
+
+    let units = [(); 3];
+    let ptr = units.as_ptr();
+    assert_eq!(ptr.distance_to((ptr as usize + 3) as *const ()), 3);
+}
+
+#[test]
+fn test_wrapping_offset() {
+    let data = [0u64; 4];
+    let ptr = data.as_ptr();
+    let before = PointerExt::wrapping_offset(ptr, -1);
+    assert_eq!(before as usize, (ptr as usize).wrapping_sub(8));
+    let mut p = before;
+    p.wrapping_inc();
+    assert_eq!(p, ptr);
+    p.wrapping_dec();
+    assert_eq!(p, before);
+
+    let mut max = !0usize as *mut u8;
+    max.wrapping_inc();
+    assert_eq!(max as usize, 0);
+}
+
+#[test]
+fn test_align() {
+    for addr in 0..40 {
+        let p = addr as *mut u32;
+        for &align in &[1, 2, 4, 16] {
+            let up = PointerExt::align_up(p, align) as usize;
+            let down = PointerExt::align_down(p, align) as usize;
+            assert!(up >= addr && up - addr < align && up % align == 0);
+            assert!(down <= addr && addr - down < align && down % align == 0);
+            assert_eq!(PointerExt::is_aligned_to(p, align), addr % align == 0);
+        }
+    }
+}
+
+#[test]
+fn test_offset_bytes() {
+    let mut data = [0u32; 4];
+    let ptr = data.as_mut_ptr();
+    unsafe {
+        let p = ptr.add_bytes(8);
+        assert_eq!(p, ptr.offset(2));
+        assert_eq!(p.sub_bytes(4), ptr.offset(1));
+        assert_eq!(p.offset_bytes(-8), ptr);
+        *(ptr as *mut u8).add_bytes(4) = 1;
+    }
+    assert_eq!(data[1], u32::from_le(1));
+}
+
+#[test]
+fn test_in_range() {
+    let data = [0u8; 4];
+    let start = data.as_ptr();
+    let end = start.wrapping_offset(4);
+    for i in 0..4 {
+        assert!(start.wrapping_offset(i).in_range(start, end));
+    }
+    assert!(!end.in_range(start, end));
+    assert!(!start.wrapping_offset(-1).in_range(start, end));
+    assert!(!start.in_range(start, start));
+    assert_eq!(start.min_ptr(end), start);
+    assert_eq!(end.min_ptr(start), start);
+    assert_eq!(start.max_ptr(end), end);
+    assert_eq!(end.max_ptr(end), end);
+}
+
+#[test]
+fn test_drop_in_place_n() {
+    use std::cell::Cell;
+
+    struct Count<'a>(&'a Cell<usize>);
+    impl<'a> Drop for Count<'a> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+    let drops = Cell::new(0);
+    let mut items = [Count(&drops), Count(&drops), Count(&drops)];
+    unsafe {
+        PointerMutExt::drop_in_place_n(items.as_mut_ptr(), 2);
+        assert_eq!(drops.get(), 2);
+        PointerMutExt::drop_in_place_n(items.as_mut_ptr(), 0);
+        assert_eq!(drops.get(), 2);
+    }
+    ::std::mem::forget(items);
+}
+
+#[test]
+fn test_add_sub() {
+    let data = [0u32; 8];
+    let ptr = data.as_ptr();
+    unsafe {
+        let p = ptr.debug_add(5);
+        assert_eq!(p, ptr.offset(5));
+        assert_eq!(p.debug_sub(5), ptr);
+    }
+    assert!(ptr.fits_offset(isize::max_value() as usize / 4));
+    assert!(!ptr.fits_offset(isize::max_value() as usize / 4 + 1));
+    assert!(!ptr.fits_offset(!0));
+}
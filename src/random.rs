@@ -0,0 +1,98 @@
+//! Small pseudorandom number generation, without dependencies
+//!
+//! This is meant for shuffling and sampling in tools, not for anything that
+//! needs statistical quality or security.
+
+/// A source of pseudorandom numbers.
+///
+/// Only `.next_u64()` is required; implement it to plug in any generator.
+pub trait RandomSource {
+    /// Return the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Return a random number in `0..n`, uniformly distributed.
+    ///
+    /// **Panics** if `n` is zero.
+    fn below(&mut self, n: usize) -> usize {
+        assert!(n != 0, "RandomSource::below: n must not be zero");
+        let n = n as u64;
+        // Lemire's method: multiply into 128 bits and reject the few values
+        // that would make the result biased.
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let m = self.next_u64() as u128 * n as u128;
+            if m as u64 >= threshold {
+                return (m >> 64) as usize;
+            }
+        }
+    }
+}
+
+impl<'a, R> RandomSource for &'a mut R
+    where R: RandomSource + ?Sized
+{
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A xorshift64* generator.
+///
+/// ```
+/// use odds::random::{RandomSource, XorShift};
+///
+/// let mut rng = XorShift::new(7);
+/// let roll = rng.below(6) + 1;
+/// assert!(1 <= roll && roll <= 6);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Create a new generator from `seed`.
+    ///
+    /// The same seed always produces the same sequence. Any seed is valid,
+    /// including zero.
+    pub fn new(seed: u64) -> Self {
+        // the state must not be zero; mix the seed so that similar seeds
+        // give different sequences
+        let state = (seed ^ 0x9E3779B97F4A7C15).wrapping_mul(0xBF58476D1CE4E5B9);
+        XorShift {
+            state: if state == 0 { 1 } else { state },
+        }
+    }
+}
+
+impl RandomSource for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+}
+
+#[test]
+fn test_xorshift() {
+    let mut a = XorShift::new(0);
+    let mut b = XorShift::new(0);
+    let mut c = XorShift::new(1);
+    let xs = (0..8).map(|_| a.next_u64()).collect::<Vec<_>>();
+    let ys = (0..8).map(|_| b.next_u64()).collect::<Vec<_>>();
+    let zs = (0..8).map(|_| c.next_u64()).collect::<Vec<_>>();
+    assert_eq!(xs, ys);
+    assert!(xs != zs);
+
+    let mut counts = [0; 5];
+    for _ in 0..5000 {
+        counts[a.below(5)] += 1;
+    }
+    for &count in &counts {
+        assert!(count > 800 && count < 1200, "{:?}", counts);
+    }
+    assert_eq!(a.below(1), 0);
+}
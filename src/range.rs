@@ -1,3 +1,5 @@
+//! Range utilities
+
 
 use std::ops::{
     RangeFull,
@@ -5,6 +7,11 @@ use std::ops::{
     RangeTo,
     Range,
 };
+#[cfg(feature="std")]
+use std::cmp::{min, max, Ordering};
+
+#[cfg(feature="std")]
+use slice::binary_search_range;
 
 /// **IndexRange** is implemented by Rust's built-in range types, produced
 /// by range syntax like `..`, `a..`, `..b` or `c..d`.
@@ -37,3 +44,104 @@ impl<T: Copy> IndexRange<T> for Range<T> {
     fn end(&self) -> Option<T> { Some(self.end) }
 }
 
+
+/// Return the index of the first range in the free list `ranges` that is
+/// at least `len` long.
+///
+/// ```
+/// use odds::range::find_first_fit;
+///
+/// let free = [0..2, 4..10, 12..15];
+/// assert_eq!(find_first_fit(&free, 3), Some(1));
+/// assert_eq!(find_first_fit(&free, 7), None);
+/// ```
+pub fn find_first_fit(ranges: &[Range<usize>], len: usize) -> Option<usize> {
+    ranges.iter().position(|r| r.end.saturating_sub(r.start) >= len)
+}
+
+/// Return the index of the shortest range in the free list `ranges` that is
+/// at least `len` long. Of several equally short ranges, the first is chosen.
+///
+/// ```
+/// use odds::range::find_best_fit;
+///
+/// let free = [0..2, 4..10, 12..15];
+/// assert_eq!(find_best_fit(&free, 3), Some(2));
+/// assert_eq!(find_best_fit(&free, 2), Some(0));
+/// ```
+pub fn find_best_fit(ranges: &[Range<usize>], len: usize) -> Option<usize> {
+    let mut best: Option<(usize, usize)> = None;
+    for (i, r) in ranges.iter().enumerate() {
+        let rlen = r.end.saturating_sub(r.start);
+        if rlen >= len && best.map_or(true, |(_, blen)| rlen < blen) {
+            if rlen == len {
+                return Some(i);
+            }
+            best = Some((i, rlen));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Insert `range` into the free list `ranges`, merging it with any ranges
+/// that it overlaps or touches.
+///
+/// `ranges` must be sorted and must not contain overlapping or touching
+/// ranges; it stays that way after the insertion. Empty ranges are not
+/// inserted.
+///
+/// Requires `feature="std"`
+///
+/// ```
+/// use odds::range::insert_and_coalesce;
+///
+/// let mut free = vec![0..2, 4..6, 10..12];
+/// insert_and_coalesce(&mut free, 2..4);
+/// assert_eq!(free, vec![0..6, 10..12]);
+/// insert_and_coalesce(&mut free, 7..8);
+/// assert_eq!(free, vec![0..6, 7..8, 10..12]);
+/// ```
+#[cfg(feature="std")]
+pub fn insert_and_coalesce(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    if range.start >= range.end {
+        return;
+    }
+    // first range that ends at or after the start of the new range
+    let i = match binary_search_range(ranges.len(), |k| {
+        if ranges[k].end < range.start { Ordering::Less } else { Ordering::Greater }
+    }) {
+        Ok(i) | Err(i) => i,
+    };
+    let mut merged = range;
+    let mut j = i;
+    while j < ranges.len() && ranges[j].start <= merged.end {
+        merged.start = min(merged.start, ranges[j].start);
+        merged.end = max(merged.end, ranges[j].end);
+        j += 1;
+    }
+    if i == j {
+        ranges.insert(i, merged);
+    } else {
+        ranges[i] = merged;
+        ranges.drain(i + 1..j);
+    }
+}
+
+#[test]
+fn test_insert_and_coalesce() {
+    let mut free = Vec::new();
+    insert_and_coalesce(&mut free, 5..5);
+    assert_eq!(free, vec![]);
+    insert_and_coalesce(&mut free, 5..6);
+    insert_and_coalesce(&mut free, 1..2);
+    insert_and_coalesce(&mut free, 8..9);
+    assert_eq!(free, vec![1..2, 5..6, 8..9]);
+    insert_and_coalesce(&mut free, 0..1);
+    assert_eq!(free, vec![0..2, 5..6, 8..9]);
+    insert_and_coalesce(&mut free, 3..10);
+    assert_eq!(free, vec![0..2, 3..10]);
+    insert_and_coalesce(&mut free, 1..20);
+    assert_eq!(free, vec![0..20]);
+    assert_eq!(find_first_fit(&free, 20), Some(0));
+    assert_eq!(find_best_fit(&free, 21), None);
+}
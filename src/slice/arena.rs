@@ -0,0 +1,185 @@
+//! A bump allocator over a borrowed byte slice
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+use std::ptr;
+use std::slice::from_raw_parts_mut;
+
+use pointer::PointerExt;
+
+/// An arena that hands out allocations from a caller-provided byte buffer.
+///
+/// Allocation bumps a fill pointer forward, padding as needed to satisfy the
+/// alignment of each requested type. Values are never dropped; the memory is
+/// reclaimed all at once with `.reset()`.
+///
+/// Allocations borrow the arena, so `.reset()` (which takes `&mut self`)
+/// can only be called when none of them are alive. Each allocation is a
+/// fresh block past the fill pointer, and only `.reset()` moves it back, so
+/// the `&mut` references handed out through `&self` never overlap.
+///
+/// ```
+/// use odds::slice::SliceArena;
+///
+/// let mut buf = [0u8; 64];
+/// let mut arena = SliceArena::new(&mut buf);
+/// {
+///     let x = arena.alloc(1u32).unwrap();
+///     let xs = arena.alloc_slice_copy(&[1u16, 2, 3]).unwrap();
+///     *x += 1;
+///     xs[0] = 7;
+///     assert_eq!(*x, 2);
+///     assert_eq!(xs, &[7, 2, 3]);
+/// }
+/// assert!(arena.used() >= 10);
+/// arena.reset();
+/// assert_eq!(arena.used(), 0);
+/// ```
+#[derive(Debug)]
+pub struct SliceArena<'a> {
+    ptr: *mut u8,
+    len: usize,
+    pos: Cell<usize>,
+    ty: PhantomData<&'a mut [u8]>,
+}
+
+#[allow(clippy::mut_from_ref)]
+impl<'a> SliceArena<'a> {
+    /// Create a new arena that allocates out of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceArena {
+            ptr: buf.as_mut_ptr(),
+            len: buf.len(),
+            pos: Cell::new(0),
+            ty: PhantomData,
+        }
+    }
+
+    /// Return the total size of the underlying buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Return the number of bytes in use, including alignment padding.
+    pub fn used(&self) -> usize {
+        self.pos.get()
+    }
+
+    /// Return the number of bytes not yet in use.
+    pub fn remaining(&self) -> usize {
+        self.len - self.pos.get()
+    }
+
+    /// Release all allocations, making the whole buffer available again.
+    pub fn reset(&mut self) {
+        self.pos.set(0);
+    }
+
+    /// Reserve `size` bytes aligned to `align`, and return a pointer
+    /// to the start of the reserved block.
+    ///
+    /// Return `None` if the remaining space is insufficient.
+    ///
+    /// **Panics** if `align` is not a power of two.
+    pub fn alloc_bytes(&self, size: usize, align: usize) -> Option<*mut u8> {
+        assert!(align.is_power_of_two());
+        let pos = self.pos.get();
+        let addr = self.ptr as usize + pos;
+        let pad = addr.wrapping_neg() & (align - 1);
+        let start = match pos.checked_add(pad) {
+            Some(s) => s,
+            None => return None,
+        };
+        match start.checked_add(size) {
+            Some(end) if end <= self.len => {
+                self.pos.set(end);
+                unsafe {
+                    Some(self.ptr.debug_add(start))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Move `value` into the arena and return a mutable reference to it.
+    ///
+    /// Return `None` if the remaining space is insufficient. The value will
+    /// not be dropped.
+    pub fn alloc<T>(&self, value: T) -> Option<&mut T> {
+        match self.alloc_bytes(size_of::<T>(), align_of::<T>()) {
+            None => None,
+            Some(p) => unsafe {
+                let p = p as *mut T;
+                ptr::write(p, value);
+                Some(&mut *p)
+            }
+        }
+    }
+
+    /// Copy the elements of `data` into the arena and return a mutable
+    /// slice of the copies.
+    ///
+    /// Return `None` if the remaining space is insufficient.
+    pub fn alloc_slice_copy<T: Copy>(&self, data: &[T]) -> Option<&mut [T]> {
+        let size = match size_of::<T>().checked_mul(data.len()) {
+            Some(s) => s,
+            None => return None,
+        };
+        match self.alloc_bytes(size, align_of::<T>()) {
+            None => None,
+            Some(p) => unsafe {
+                let p = p as *mut T;
+                ptr::copy_nonoverlapping(data.as_ptr(), p, data.len());
+                Some(from_raw_parts_mut(p, data.len()))
+            }
+        }
+    }
+
+    /// Allocate a slice of `len` clones of `elt` and return it.
+    ///
+    /// Return `None` if the remaining space is insufficient. The elements will
+    /// not be dropped.
+    pub fn alloc_slice_fill<T: Clone>(&self, len: usize, elt: T) -> Option<&mut [T]> {
+        let size = match size_of::<T>().checked_mul(len) {
+            Some(s) => s,
+            None => return None,
+        };
+        match self.alloc_bytes(size, align_of::<T>()) {
+            None => None,
+            Some(p) => unsafe {
+                let p = p as *mut T;
+                for i in 0..len {
+                    ptr::write(p.debug_add(i), elt.clone());
+                }
+                Some(from_raw_parts_mut(p, len))
+            }
+        }
+    }
+}
+
+#[test]
+fn test_arena_align() {
+    let mut buf = [0u8; 32];
+    let arena = SliceArena::new(&mut buf);
+    let a = arena.alloc(1u8).unwrap() as *mut u8 as usize;
+    let b = arena.alloc(2u64).unwrap() as *mut u64 as usize;
+    assert_eq!(b % align_of::<u64>(), 0);
+    assert!(b > a);
+    assert!(arena.alloc([0u64; 4]).is_none());
+    assert!(arena.alloc_slice_fill(arena.remaining(), 0u8).is_some());
+    assert_eq!(arena.remaining(), 0);
+    assert!(arena.alloc(0u8).is_none());
+    assert!(arena.alloc(()).is_some());
+}
+
+#[test]
+fn test_arena_reset() {
+    let mut buf = [0u8; 8];
+    let mut arena = SliceArena::new(&mut buf);
+    assert_eq!(arena.alloc_slice_copy(&[1u8; 8]).map(|s| s.len()), Some(8));
+    assert!(arena.alloc(1u8).is_none());
+    arena.reset();
+    assert_eq!(arena.alloc(1u8).map(|x| *x), Some(1));
+    assert_eq!(arena.used(), 1);
+}
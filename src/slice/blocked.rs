@@ -3,9 +3,10 @@
 
 use std::mem::size_of;
 use std::marker::PhantomData;
+use std::slice::from_raw_parts;
 
 use rawslice::SliceIter;
-use rawpointer::ptrdistance;
+use pointer::PointerExt;
 
 pub unsafe trait Block {
     type Item;
@@ -77,7 +78,7 @@ impl<'a, B, T> BlockedIter<'a, B, T>
         unsafe {
             let ptr = data.as_ptr();
             let len = data.len();
-            let end = ptr.offset(len as isize);
+            let end = ptr.debug_add(len);
             BlockedIter {
                 ptr: ptr,
                 end: end,
@@ -105,7 +106,7 @@ impl<'a, B, T> BlockedIter<'a, B, T>
     /// Return the next iterator element, without stepping the iterator.
     pub fn peek_next(&self) -> Option<<Self as Iterator>::Item>
     {
-        if ptrdistance(self.ptr, self.end) >= B::capacity() {
+        if self.ptr.distance_to(self.end) >= B::capacity() {
             unsafe {
                 Some(&*(self.ptr as *const B))
             }
@@ -120,10 +121,10 @@ impl<'a, B, T> Iterator for BlockedIter<'a, B, T>
 {
     type Item = &'a B;
     fn next(&mut self) -> Option<Self::Item> {
-        if ptrdistance(self.ptr, self.end) >= B::capacity() {
+        if self.ptr.distance_to(self.end) >= B::capacity() {
             unsafe {
                 let elt = Some(&*(self.ptr as *const B));
-                self.ptr = self.ptr.offset(B::capacity() as isize);
+                self.ptr = self.ptr.debug_add(B::capacity());
                 elt
             }
         } else {
@@ -132,7 +133,7 @@ impl<'a, B, T> Iterator for BlockedIter<'a, B, T>
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = ptrdistance(self.ptr, self.end) / B::capacity();
+        let len = self.ptr.distance_to(self.end) / B::capacity();
         (len, Some(len))
     }
 }
@@ -150,12 +151,117 @@ impl<'a, B, T> Index<usize> for BlockedIter<'a, B, T>
     fn index(&self, i: usize) -> &Self::Output {
         assert!(i < self.len());
         unsafe {
-            &*(self.ptr.offset((i * B::capacity()) as isize) as *const B)
+            &*(self.ptr.debug_add(i * B::capacity()) as *const B)
         }
     }
 }
 
 
+/// An iterator that yields fixed size array references `&[T; N]` out of
+/// a slice, front to back.
+///
+/// The elements at the end that don't fill a whole block are available
+/// through `.tail()`.
+///
+/// ```
+/// use odds::slice::blocked::Blocks;
+///
+/// let data = [1, 2, 3, 4, 5, 6, 7];
+/// let mut blocks = Blocks::<_, 3>::new(&data);
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks.next(), Some(&[1, 2, 3]));
+/// assert_eq!(blocks.next(), Some(&[4, 5, 6]));
+/// assert_eq!(blocks.next(), None);
+/// assert_eq!(blocks.tail(), &[7]);
+/// ```
+#[derive(Debug)]
+pub struct Blocks<'a, T: 'a, const N: usize> {
+    ptr: *const T,
+    end: *const T,
+    tail: *const T,
+    tail_end: *const T,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T, const N: usize> Copy for Blocks<'a, T, N> { }
+impl<'a, T, const N: usize> Clone for Blocks<'a, T, N> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T, const N: usize> Blocks<'a, T, N> {
+    /// Create a `Blocks` iterator over the whole blocks of `data`.
+    ///
+    /// **Panics** if `N` is zero or if `T` is a zero-sized type.
+    pub fn new(data: &'a [T]) -> Self {
+        assert!(N != 0);
+        assert!(size_of::<T>() != 0);
+        unsafe {
+            let ptr = data.as_ptr();
+            let len = data.len();
+            let tail = ptr.debug_add(len - len % N);
+            Blocks {
+                ptr: ptr,
+                end: tail,
+                tail: tail,
+                tail_end: ptr.debug_add(len),
+                ty: PhantomData,
+            }
+        }
+    }
+
+    /// Return the elements at the end of the slice that don't make up a
+    /// whole block.
+    pub fn tail(&self) -> &'a [T] {
+        unsafe {
+            from_raw_parts(self.tail, self.tail.distance_to(self.tail_end))
+        }
+    }
+
+    /// Return the blocks that have not been yielded yet, as a slice.
+    pub fn as_slice(&self) -> &'a [[T; N]] {
+        unsafe {
+            from_raw_parts(self.ptr as *const [T; N], self.ptr.distance_to(self.end) / N)
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Blocks<'a, T, N> {
+    type Item = &'a [T; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr != self.end {
+            unsafe {
+                let elt = &*(self.ptr as *const [T; N]);
+                self.ptr = self.ptr.debug_add(N);
+                Some(elt)
+            }
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ptr.distance_to(self.end) / N;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Blocks<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.ptr != self.end {
+            unsafe {
+                self.end = self.end.debug_sub(N);
+                Some(&*(self.end as *const [T; N]))
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Blocks<'a, T, N> { }
+
 #[test]
 fn test_blocked() {
     let data = [0, 1, 2, 3, 4];
@@ -185,3 +291,22 @@ fn test_blocked_index_oob() {
     let iter = BlockedIter::<[u32; 2], _>::from_slice(&data);
     iter[2];
 }
+
+#[test]
+fn test_blocks() {
+    let data = [0, 1, 2, 3, 4];
+    let mut iter = Blocks::<_, 2>::new(&data);
+    assert_eq!(iter.as_slice(), &[[0, 1], [2, 3]]);
+    assert_eq!(iter.next_back(), Some(&[2, 3]));
+    assert_eq!(iter.tail(), &[4]);
+    assert_eq!(iter.next(), Some(&[0, 1]));
+    assert_eq!(iter.next_back(), None);
+
+    let mut iter = Blocks::<_, 8>::new(&data);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.tail(), &data);
+
+    assert_eq!(Blocks::<_, 1>::new(&data).count(), 5);
+    assert_eq!(Blocks::<_, 5>::new(&data).tail(), &[]);
+}
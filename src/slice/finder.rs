@@ -0,0 +1,198 @@
+//! Substring search with the two-way algorithm
+//!
+//! A `Finder` does the preprocessing of a needle once, so that it can be
+//! searched for in many haystacks.
+
+use std::cmp::max;
+
+/// A precomputed searcher for a needle, using the two-way string matching
+/// algorithm of Crochemore and Perrin.
+///
+/// Searching takes `O(n + m)` time in the worst case, for a haystack of
+/// length `n` and a needle of length `m`, and no extra space.
+///
+/// ```
+/// use odds::slice::finder::Finder;
+///
+/// let finder = Finder::new(b"needle");
+/// assert_eq!(finder.find(b"haystack with a needle in it"), Some(16));
+/// assert_eq!(finder.find(b"haystack"), None);
+/// assert_eq!(finder.find_str("a needle"), Some(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Finder<'n> {
+    needle: &'n [u8],
+    /// The critical factorization splits the needle at this index
+    crit_pos: usize,
+    /// The period of the needle, or a lower bound for it if `long_period`
+    period: usize,
+    /// If the needle is not periodic, no part of a match can be reused
+    /// after a mismatch, so there is no need to remember how much matched
+    long_period: bool,
+    /// Bit `b % 64` is set for each byte `b` in the needle
+    byteset: u64,
+}
+
+impl<'n> Finder<'n> {
+    /// Create a new `Finder` for `needle`.
+    pub fn new(needle: &'n [u8]) -> Self {
+        let (pos_less, period_less) = maximal_suffix(needle, false);
+        let (pos_greater, period_greater) = maximal_suffix(needle, true);
+        let (crit_pos, period) = if pos_less > pos_greater {
+            (pos_less, period_less)
+        } else {
+            (pos_greater, period_greater)
+        };
+        let byteset = needle.iter().fold(0, |set, &b| set | 1 << (b & 63));
+
+        // the needle is periodic if the part before the critical position
+        // occurs again one period later
+        if crit_pos + period <= needle.len() &&
+            needle[..crit_pos] == needle[period..period + crit_pos]
+        {
+            Finder {
+                needle: needle,
+                crit_pos: crit_pos,
+                period: period,
+                long_period: false,
+                byteset: byteset,
+            }
+        } else {
+            Finder {
+                needle: needle,
+                crit_pos: crit_pos,
+                period: max(crit_pos, needle.len() - crit_pos) + 1,
+                long_period: true,
+                byteset: byteset,
+            }
+        }
+    }
+
+    /// Return the needle.
+    pub fn needle(&self) -> &'n [u8] {
+        self.needle
+    }
+
+    /// Return the index of the first occurrence of the needle in
+    /// `haystack`, or `None`.
+    ///
+    /// An empty needle is found at index zero.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = self.needle;
+        let n = needle.len();
+        if n == 0 {
+            return Some(0);
+        }
+        let mut pos = 0;
+        // needle[..memory] is known to match at pos
+        let mut memory = 0;
+        while pos + n <= haystack.len() {
+            // skip ahead if the last byte of the window is not in the needle
+            let last = haystack[pos + n - 1];
+            if (self.byteset >> (last & 63)) & 1 == 0 {
+                pos += n;
+                memory = 0;
+                continue;
+            }
+
+            // match the right part of the needle, front to back
+            let mut i = if self.long_period {
+                self.crit_pos
+            } else {
+                max(self.crit_pos, memory)
+            };
+            while i < n && needle[i] == haystack[pos + i] {
+                i += 1;
+            }
+            if i < n {
+                pos += i - self.crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            // match the left part of the needle, back to front
+            let start = if self.long_period { 0 } else { memory };
+            let mut j = self.crit_pos;
+            while j > start && needle[j - 1] == haystack[pos + j - 1] {
+                j -= 1;
+            }
+            if j <= start {
+                return Some(pos);
+            }
+            pos += self.period;
+            if !self.long_period {
+                memory = n - self.period;
+            }
+        }
+        None
+    }
+
+    /// Return the byte index of the first occurrence of the needle in
+    /// `haystack`, or `None`.
+    ///
+    /// If the needle is valid UTF-8, the index is always on a char
+    /// boundary.
+    pub fn find_str(&self, haystack: &str) -> Option<usize> {
+        self.find(haystack.as_bytes())
+    }
+}
+
+/// Return the start and period of the maximal suffix of `arr`, in
+/// lexicographic order or reversed lexicographic order if `order_greater`.
+fn maximal_suffix(arr: &[u8], order_greater: bool) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+    while let Some(&a) = arr.get(right + offset) {
+        let b = arr[left + offset];
+        if (a < b && !order_greater) || (a > b && order_greater) {
+            // the suffix at right is smaller; the period is everything so far
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            // advance through a repetition of the current period
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // the suffix at right is larger; start over from there
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+    (left, period)
+}
+
+#[test]
+fn test_finder() {
+    use random::{RandomSource, XorShift};
+
+    fn naive(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        (0..haystack.len() + 1).find(|&i| haystack[i..].starts_with(needle))
+    }
+
+    let mut rng = XorShift::new(23);
+    // small alphabets make for periodic needles and many partial matches
+    for &alphabet in &[1, 2, 3, 26] {
+        for _ in 0..300 {
+            let hlen = rng.below(60);
+            let nlen = rng.below(8);
+            let haystack = (0..hlen).map(|_| b'a' + rng.below(alphabet) as u8)
+                                    .collect::<Vec<_>>();
+            let needle = (0..nlen).map(|_| b'a' + rng.below(alphabet) as u8)
+                                  .collect::<Vec<_>>();
+            assert_eq!(Finder::new(&needle).find(&haystack), naive(&haystack, &needle),
+                       "{:?} {:?}", haystack, needle);
+        }
+    }
+    let finder = Finder::new(b"abaabaabaab");
+    assert_eq!(finder.find(b"abaabaabaaabaabaabaabaab"), Some(10));
+    assert_eq!(Finder::new("γδ".as_bytes()).find_str("αβγδ"), Some(4));
+}
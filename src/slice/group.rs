@@ -0,0 +1,142 @@
+//! An iterator of runs of consecutive elements
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::slice::from_raw_parts;
+
+use pointer::PointerExt;
+
+/// An iterator over a slice that yields maximal runs of consecutive elements,
+/// where the predicate `pred(a, b)` holds for each pair of adjacent elements
+/// `a, b` inside the same run.
+///
+/// Iterator element type is `&'a [T]`; no yielded run is empty.
+///
+/// ```
+/// use odds::slice::group::GroupBy;
+///
+/// let data = [1, 1, 2, 3, 3, 3, 1];
+/// let runs = GroupBy::new(&data, |a, b| a == b);
+/// assert!(runs.eq(vec![&[1, 1][..], &[2], &[3, 3, 3], &[1]]));
+///
+/// // Split into strictly ascending sequences
+/// let data = [1, 2, 5, 3, 4, 0];
+/// let mut runs = GroupBy::new(&data, |a, b| a < b);
+/// assert_eq!(runs.next_back(), Some(&[0][..]));
+/// assert_eq!(runs.next(), Some(&[1, 2, 5][..]));
+/// assert_eq!(runs.next(), Some(&[3, 4][..]));
+/// assert_eq!(runs.next(), None);
+/// ```
+pub struct GroupBy<'a, T: 'a, P> {
+    ptr: *const T,
+    end: *const T,
+    pred: P,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T, P> GroupBy<'a, T, P>
+    where P: FnMut(&T, &T) -> bool,
+{
+    /// Create a new `GroupBy` iterator over the runs of `data`.
+    ///
+    /// Panics if `T` is a zero-sized type. That case is not supported.
+    pub fn new(data: &'a [T], pred: P) -> Self {
+        assert!(size_of::<T>() != 0);
+        unsafe {
+            let ptr = data.as_ptr();
+            GroupBy {
+                ptr: ptr,
+                end: ptr.debug_add(data.len()),
+                pred: pred,
+                ty: PhantomData,
+            }
+        }
+    }
+
+    /// Return the part of the slice that has not been yielded yet.
+    pub fn remainder(&self) -> &'a [T] {
+        unsafe {
+            from_raw_parts(self.ptr, self.ptr.distance_to(self.end))
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for GroupBy<'a, T, P>
+    where P: FnMut(&T, &T) -> bool,
+{
+    type Item = &'a [T];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ptr == self.end {
+            return None;
+        }
+        unsafe {
+            let start = self.ptr;
+            let mut last = start;
+            let mut next = last.offset(1);
+            while next != self.end && (self.pred)(&*last, &*next) {
+                last = next;
+                next = next.offset(1);
+            }
+            self.ptr = next;
+            Some(from_raw_parts(start, start.distance_to(next)))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ptr.distance_to(self.end);
+        ((len != 0) as usize, Some(len))
+    }
+}
+
+impl<'a, T, P> DoubleEndedIterator for GroupBy<'a, T, P>
+    where P: FnMut(&T, &T) -> bool,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.ptr == self.end {
+            return None;
+        }
+        unsafe {
+            let end = self.end;
+            let mut first = end.offset(-1);
+            while first != self.ptr && (self.pred)(&*first.offset(-1), &*first) {
+                first = first.offset(-1);
+            }
+            self.end = first;
+            Some(from_raw_parts(first, first.distance_to(end)))
+        }
+    }
+}
+
+impl<'a, T, P> fmt::Debug for GroupBy<'a, T, P>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rest = unsafe {
+            from_raw_parts(self.ptr, self.ptr.distance_to(self.end))
+        };
+        f.debug_struct("GroupBy")
+         .field("remainder", &rest)
+         .finish()
+    }
+}
+
+#[test]
+fn test_group_by() {
+    let data = [0, 0, 1, 1, 1, 2];
+    let mut iter = GroupBy::new(&data, |a, b| a == b);
+    assert_eq!(iter.next(), Some(&[0, 0][..]));
+    assert_eq!(iter.remainder(), &[1, 1, 1, 2]);
+    assert_eq!(iter.next_back(), Some(&[2][..]));
+    assert_eq!(iter.next_back(), Some(&[1, 1, 1][..]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let empty: [i32; 0] = [];
+    assert_eq!(GroupBy::new(&empty, |a, b| a == b).next(), None);
+
+    let data = [1, 2, 3];
+    assert_eq!(GroupBy::new(&data, |_, _| true).count(), 1);
+    assert_eq!(GroupBy::new(&data, |_, _| false).count(), 3);
+    assert!(GroupBy::new(&data, |_, _| false).rev().eq(vec![&[3][..], &[2], &[1]]));
+}
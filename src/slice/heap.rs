@@ -0,0 +1,200 @@
+//! Binary max-heap operations on slices
+//!
+//! A slice is a heap if each element at index `i` is not less than its
+//! children at `2 * i + 1` and `2 * i + 2`; the maximum is then at index
+//! zero. Unlike `BinaryHeap`, these work in any storage, for example a
+//! fixed size buffer:
+//!
+//! ```
+//! use odds::slice::heap::{push_heap, pop_heap};
+//!
+//! let mut buf = [0; 8];
+//! let mut len = 0;
+//! for &x in &[3, 9, 1, 7] {
+//!     buf[len] = x;
+//!     len += 1;
+//!     push_heap(&mut buf[..len]);
+//! }
+//! pop_heap(&mut buf[..len]);
+//! len -= 1;
+//! assert_eq!(buf[len], 9);
+//! assert_eq!(buf[0], 7);
+//! ```
+//!
+//! The `_by` variants take a `less_than` function that returns `true` if
+//! its first argument is ordered before its second.
+
+/// Move the element at `index` up towards the root until its parent is not
+/// less than it.
+///
+/// Use after increasing an element of a heap.
+///
+/// **Panics** if `index` is out of bounds.
+pub fn sift_up<T>(heap: &mut [T], index: usize)
+    where T: Ord
+{
+    sift_up_by(heap, index, |a, b| a < b)
+}
+
+/// Move the element at `index` up towards the root until its parent is not
+/// less than it, in the order given by `less_than`.
+///
+/// **Panics** if `index` is out of bounds.
+pub fn sift_up_by<T, F>(heap: &mut [T], mut index: usize, mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    assert!(index < heap.len(), "sift_up: index out of bounds");
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if !less_than(&heap[parent], &heap[index]) {
+            return;
+        }
+        heap.swap(parent, index);
+        index = parent;
+    }
+}
+
+/// Move the element at `index` down until it is not less than its
+/// children.
+///
+/// Use after decreasing an element of a heap. Also restores the heap
+/// property below `index` if both children of `index` are heaps.
+///
+/// **Panics** if `index` is out of bounds.
+pub fn sift_down<T>(heap: &mut [T], index: usize)
+    where T: Ord
+{
+    sift_down_by(heap, index, |a, b| a < b)
+}
+
+/// Move the element at `index` down until it is not less than its
+/// children, in the order given by `less_than`.
+///
+/// **Panics** if `index` is out of bounds.
+pub fn sift_down_by<T, F>(heap: &mut [T], mut index: usize, mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    assert!(index < heap.len(), "sift_down: index out of bounds");
+    loop {
+        let mut child = 2 * index + 1;
+        if child >= heap.len() {
+            return;
+        }
+        if child + 1 < heap.len() && less_than(&heap[child], &heap[child + 1]) {
+            child += 1;
+        }
+        if !less_than(&heap[index], &heap[child]) {
+            return;
+        }
+        heap.swap(index, child);
+        index = child;
+    }
+}
+
+/// Reorder `data` into a heap, in linear time.
+pub fn make_heap<T>(data: &mut [T])
+    where T: Ord
+{
+    make_heap_by(data, |a, b| a < b)
+}
+
+/// Reorder `data` into a heap, in the order given by `less_than`.
+pub fn make_heap_by<T, F>(data: &mut [T], mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    for i in (0..data.len() / 2).rev() {
+        sift_down_by(data, i, &mut less_than);
+    }
+}
+
+/// Add the last element of `heap` to the heap in the rest of the slice.
+///
+/// `heap[..heap.len() - 1]` must be a heap; afterwards all of `heap` is.
+/// Does nothing if `heap` is empty.
+pub fn push_heap<T>(heap: &mut [T])
+    where T: Ord
+{
+    push_heap_by(heap, |a, b| a < b)
+}
+
+/// Add the last element of `heap` to the heap in the rest of the slice, in
+/// the order given by `less_than`.
+pub fn push_heap_by<T, F>(heap: &mut [T], less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    if let Some(last) = heap.len().checked_sub(1) {
+        sift_up_by(heap, last, less_than);
+    }
+}
+
+/// Move the maximum of `heap` to the end of the slice, and make the rest of
+/// the slice a heap again.
+///
+/// `heap` must be a heap; afterwards `heap[..heap.len() - 1]` is.
+/// Does nothing if `heap` is empty.
+pub fn pop_heap<T>(heap: &mut [T])
+    where T: Ord
+{
+    pop_heap_by(heap, |a, b| a < b)
+}
+
+/// Move the maximum of `heap` to the end of the slice, and make the rest of
+/// the slice a heap again, in the order given by `less_than`.
+pub fn pop_heap_by<T, F>(heap: &mut [T], less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    if let Some(last) = heap.len().checked_sub(1) {
+        heap.swap(0, last);
+        if last > 0 {
+            sift_down_by(&mut heap[..last], 0, less_than);
+        }
+    }
+}
+
+#[cfg(test)]
+fn is_heap<T: Ord>(heap: &[T]) -> bool {
+    (1..heap.len()).all(|i| heap[(i - 1) / 2] >= heap[i])
+}
+
+#[test]
+fn test_heap() {
+    use random::{RandomSource, XorShift};
+
+    let mut rng = XorShift::new(13);
+    for len in 0..50 {
+        let mut data = (0..len).map(|_| rng.below(20)).collect::<Vec<_>>();
+        let mut sorted = data.clone();
+        sorted.sort();
+
+        make_heap(&mut data);
+        assert!(is_heap(&data));
+        for end in (0..len).rev() {
+            pop_heap(&mut data[..end + 1]);
+            assert!(is_heap(&data[..end]));
+        }
+        assert_eq!(data, sorted);
+
+        let mut heap = Vec::new();
+        for &x in &sorted {
+            heap.push(x);
+            push_heap(&mut heap);
+            assert!(is_heap(&heap));
+        }
+        if len > 0 {
+            heap[len / 2] += 25;
+            sift_up(&mut heap, len / 2);
+            assert!(is_heap(&heap));
+            heap[0] = 0;
+            sift_down(&mut heap, 0);
+            assert!(is_heap(&heap));
+        }
+    }
+
+    // a min-heap
+    let mut data = [5, 2, 8, 1];
+    make_heap_by(&mut data, |a, b| a > b);
+    assert_eq!(data[0], 1);
+    pop_heap_by(&mut data, |a, b| a > b);
+    assert_eq!(data[0], 2);
+    assert_eq!(data[3], 1);
+}
@@ -1,8 +1,15 @@
 //! Slice iterators
 
-use std::mem::size_of;
+use std::cmp::{min, Ordering};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem::{self, align_of, size_of, MaybeUninit};
 use std::marker::PhantomData;
 use std::ops::Index;
+use std::ptr;
+use std::slice::from_raw_parts;
+
+use pointer::PointerExt;
 
 pub use rawslice::SliceIter;
 
@@ -50,6 +57,13 @@ impl<'a, T> SliceCopyIter<'a, T>
         (self.ptr, self.end)
     }
 
+    /// Return the remaining elements as a slice.
+    pub fn as_slice(&self) -> &'a [T] {
+        unsafe {
+            from_raw_parts(self.ptr, self.len())
+        }
+    }
+
     /// Return the start pointer
     pub fn start(&self) -> *const T {
         self.ptr
@@ -86,6 +100,43 @@ impl<'a, T> SliceCopyIter<'a, T>
             None
         }
     }
+
+    /// Return an iterator of each `N` consecutive remaining elements, by
+    /// value, in a sliding window that advances one element at a time.
+    ///
+    /// **Panics** if `N` is zero.
+    ///
+    /// ```
+    /// use odds::slice::iter::SliceCopyIter;
+    ///
+    /// let data = [1, 2, 3, 4];
+    /// let windows = SliceCopyIter::from(&data[..]).array_windows::<3>();
+    /// assert!(windows.eq(vec![[1, 2, 3], [2, 3, 4]]));
+    /// ```
+    pub fn array_windows<const N: usize>(self) -> ArrayWindows<'a, T, N> {
+        assert!(N != 0);
+        ArrayWindows {
+            ptr: self.ptr,
+            end: self.end,
+            ty: PhantomData,
+        }
+    }
+
+    /// Return an iterator of each pair of consecutive remaining elements,
+    /// by value.
+    ///
+    /// ```
+    /// use odds::slice::iter::SliceCopyIter;
+    ///
+    /// let samples = [1, 4, 9, 16];
+    /// let deltas = SliceCopyIter::from(&samples[..]).pairs().map(|(a, b)| b - a);
+    /// assert!(deltas.eq(vec![3, 5, 7]));
+    /// ```
+    pub fn pairs(self) -> Pairs<'a, T> {
+        Pairs {
+            iter: self.array_windows(),
+        }
+    }
 }
 
 impl<'a, T> Iterator for SliceCopyIter<'a, T>
@@ -114,6 +165,19 @@ impl<'a, T> Iterator for SliceCopyIter<'a, T>
         self.len()
     }
 
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.len() {
+            unsafe {
+                self.ptr = self.ptr.debug_add(n);
+            }
+            self.next()
+        } else {
+            self.ptr = self.end;
+            None
+        }
+    }
+
     fn last(mut self) -> Option<Self::Item> {
         self.next_back()
     }
@@ -134,6 +198,19 @@ impl<'a, T> DoubleEndedIterator for SliceCopyIter<'a, T>
             None
         }
     }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.len() {
+            unsafe {
+                self.end = self.end.debug_sub(n);
+            }
+            self.next_back()
+        } else {
+            self.end = self.ptr;
+            None
+        }
+    }
 }
 
 impl<'a, T> ExactSizeIterator for SliceCopyIter<'a, T> where T: Copy { }
@@ -145,7 +222,7 @@ impl<'a, T> From<&'a [T]> for SliceCopyIter<'a, T>
         assert!(size_of::<T>() != 0);
         unsafe {
             let ptr = slice.as_ptr();
-            let end = ptr.offset(slice.len() as isize);
+            let end = ptr.debug_add(slice.len());
             SliceCopyIter::new(ptr, end)
         }
     }
@@ -156,12 +233,146 @@ impl<'a, T> Default for SliceCopyIter<'a, T>
 {
     /// Create an empty `SliceCopyIter`.
     fn default() -> Self {
+        // a dangling but well aligned pointer
+        let ptr = align_of::<T>() as *const T;
         unsafe {
-            SliceCopyIter::new(0x1 as *const T, 0x1 as *const T)
+            SliceCopyIter::new(ptr, ptr)
+        }
+    }
+}
+
+/// An iterator of each `N` consecutive elements of a slice, by value.
+///
+/// See [`.array_windows()`](struct.SliceCopyIter.html#method.array_windows)
+/// for more information.
+#[derive(Debug)]
+pub struct ArrayWindows<'a, T: 'a, const N: usize> {
+    ptr: *const T,
+    end: *const T,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T, const N: usize> Copy for ArrayWindows<'a, T, N> { }
+impl<'a, T, const N: usize> Clone for ArrayWindows<'a, T, N> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T, const N: usize> Iterator for ArrayWindows<'a, T, N>
+    where T: Copy
+{
+    type Item = [T; N];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len() != 0 {
+            unsafe {
+                let elt = ptr::read(self.ptr as *const [T; N]);
+                self.ptr = self.ptr.offset(1);
+                Some(elt)
+            }
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ptr.distance_to(self.end);
+        let windows = if len >= N { len - N + 1 } else { 0 };
+        (windows, Some(windows))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayWindows<'a, T, N>
+    where T: Copy
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len() != 0 {
+            unsafe {
+                let elt = ptr::read(self.end.debug_sub(N) as *const [T; N]);
+                self.end = self.end.offset(-1);
+                Some(elt)
+            }
+        } else {
+            None
         }
     }
 }
 
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayWindows<'a, T, N>
+    where T: Copy
+{ }
+
+/// An iterator of each pair of consecutive elements of a slice, by value.
+///
+/// See [`.pairs()`](struct.SliceCopyIter.html#method.pairs) for more
+/// information.
+#[derive(Copy, Clone, Debug)]
+pub struct Pairs<'a, T: 'a> {
+    iter: ArrayWindows<'a, T, 2>,
+}
+
+impl<'a, T> Iterator for Pairs<'a, T>
+    where T: Copy
+{
+    type Item = (T, T);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|[a, b]| (a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Pairs<'a, T>
+    where T: Copy
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|[a, b]| (a, b))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Pairs<'a, T> where T: Copy { }
+
+/// `SliceCopyIter` compares by its remaining elements.
+impl<'a, 'b, T> PartialEq<SliceCopyIter<'b, T>> for SliceCopyIter<'a, T>
+    where T: Copy + PartialEq
+{
+    fn eq(&self, rhs: &SliceCopyIter<'b, T>) -> bool {
+        self.as_slice() == rhs.as_slice()
+    }
+}
+
+impl<'a, T> Eq for SliceCopyIter<'a, T> where T: Copy + Eq { }
+
+/// `SliceCopyIter` orders by its remaining elements.
+impl<'a, 'b, T> PartialOrd<SliceCopyIter<'b, T>> for SliceCopyIter<'a, T>
+    where T: Copy + PartialOrd
+{
+    fn partial_cmp(&self, rhs: &SliceCopyIter<'b, T>) -> Option<Ordering> {
+        self.as_slice().partial_cmp(rhs.as_slice())
+    }
+}
+
+impl<'a, T> Ord for SliceCopyIter<'a, T>
+    where T: Copy + Ord
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.as_slice().cmp(rhs.as_slice())
+    }
+}
+
+/// `SliceCopyIter` hashes like a slice of its remaining elements.
+impl<'a, T> Hash for SliceCopyIter<'a, T>
+    where T: Copy + Hash
+{
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.as_slice().hash(h)
+    }
+}
+
 impl<'a, T> Index<usize> for SliceCopyIter<'a, T>
     where T: Copy
 {
@@ -169,8 +380,2205 @@ impl<'a, T> Index<usize> for SliceCopyIter<'a, T>
     fn index(&self, i: usize) -> &T {
         assert!(i < self.len());
         unsafe {
-            &*self.ptr.offset(i as isize)
+            &*self.ptr.debug_add(i)
+        }
+    }
+}
+
+
+#[test]
+fn test_copy_iter_nth() {
+    let data = [1, 2, 3, 4, 5];
+    let mut iter = SliceCopyIter::from(&data[..]);
+    assert_eq!(iter.nth(1), Some(2));
+    assert_eq!(iter.nth_back(1), Some(4));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.nth_back(1), None);
+    assert_eq!(iter.len(), 0);
+
+    let mut iter = SliceCopyIter::from(&data[..]);
+    assert_eq!(iter.nth(5), None);
+    assert_eq!(iter.next_back(), None);
+
+    let iter = SliceCopyIter::from(&data[..]);
+    assert_eq!(iter.rev().last(), Some(1));
+    assert_eq!(iter.rev().count(), 5);
+    assert_eq!(iter.rev().nth(4), Some(1));
+}
+
+#[test]
+fn test_copy_iter_cmp() {
+    use std::collections::HashSet;
+
+    let data = [1, 2, 3, 1, 2];
+    let a = SliceCopyIter::from(&data[..2]);
+    let mut b = SliceCopyIter::from(&data[2..]);
+    assert!(a != b);
+    assert!(a < b);
+    b.next();
+    assert_eq!(a, b);
+    assert_eq!(a.as_slice(), &[1, 2]);
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(set.contains(&b));
+    assert_eq!(SliceCopyIter::<i32>::default(), SliceCopyIter::from(&data[..0]));
+}
+
+#[test]
+fn test_array_windows() {
+    let data = [1, 2, 3, 4, 5];
+    let iter = SliceCopyIter::from(&data[..]);
+    let mut windows = iter.array_windows::<2>();
+    assert_eq!(windows.len(), 4);
+    assert_eq!(windows.next_back(), Some([4, 5]));
+    assert_eq!(windows.next(), Some([1, 2]));
+    assert!(windows.eq(vec![[2, 3], [3, 4]]));
+    assert_eq!(iter.array_windows::<5>().len(), 1);
+    assert_eq!(iter.array_windows::<6>().len(), 0);
+    assert_eq!(iter.array_windows::<6>().next_back(), None);
+    assert!(iter.pairs().rev().eq(vec![(4, 5), (3, 4), (2, 3), (1, 2)]));
+    assert_eq!(iter.array_windows::<1>().count(), 5);
+}
+
+/// The saved position of a slice iterator; see `IterCheckpoint`.
+#[derive(Debug)]
+pub struct Checkpoint<'a, T: 'a> {
+    ptr: *const T,
+    end: *const T,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T> Copy for Checkpoint<'a, T> { }
+impl<'a, T> Clone for Checkpoint<'a, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Cheap backtracking for slice iterators: save the current front and back
+/// positions, and restore them later.
+///
+/// ```
+/// use odds::slice::iter::{IterCheckpoint, SliceIter};
+///
+/// let tokens = ["let", "x", "=", "1"];
+/// let mut iter = SliceIter::from(&tokens[..]);
+/// let start = iter.checkpoint();
+/// assert_eq!(iter.next(), Some(&"let"));
+/// assert_eq!(iter.next_back(), Some(&"1"));
+/// iter.rollback(start);
+/// assert_eq!(iter.len(), 4);
+/// ```
+pub trait IterCheckpoint<'a> {
+    type Elem: 'a;
+    /// Save the iterator's current position.
+    fn checkpoint(&self) -> Checkpoint<'a, Self::Elem>;
+
+    /// Restore the iterator to a previously saved position.
+    ///
+    /// A checkpoint from any iterator over data of the same lifetime is
+    /// valid, not only one from the same iterator.
+    fn rollback(&mut self, checkpoint: Checkpoint<'a, Self::Elem>);
+
+    /// Save the iterator's current position as offsets into `base`, the
+    /// slice it is iterating.
+    ///
+    /// Unlike a `Checkpoint`, the saved state does not hold on to any
+    /// pointers, so it can be used to resume iteration after the data has
+    /// been moved or reallocated.
+    ///
+    /// Return `None` if the iterator's range is not inside `base`. Also
+    /// return `None` if the element type is zero-sized, since the position
+    /// of such an iterator can't be recovered from its pointers.
+    ///
+    /// ```
+    /// use odds::slice::iter::{IterCheckpoint, SliceIter};
+    ///
+    /// let mut data = vec![1, 2, 3];
+    /// let mut iter = SliceIter::from(&data[..]);
+    /// iter.next();
+    /// let state = iter.save(&data).unwrap();
+    ///
+    /// data.extend(vec![4, 5, 6]);
+    /// let iter = SliceIter::resume(&data, state).unwrap();
+    /// assert_eq!(iter.as_slice(), &[2, 3]);
+    /// ```
+    fn save(&self, base: &[Self::Elem]) -> Option<IterState> {
+        let cp = self.checkpoint();
+        let size = size_of::<Self::Elem>();
+        if size == 0 {
+            return None;
+        }
+        let base_start = base.as_ptr() as usize;
+        let base_end = base_start + base.len() * size;
+        let (start, end) = (cp.ptr as usize, cp.end as usize);
+        if base_start <= start && start <= end && end <= base_end {
+            Some(IterState {
+                start: (start - base_start) / size,
+                end: (end - base_start) / size,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create an iterator over `data` at a position saved with `.save()`.
+    ///
+    /// Return `None` if the saved range is out of bounds for `data`.
+    fn resume(data: &'a [Self::Elem], state: IterState) -> Option<Self>
+        where Self: Sized;
+}
+
+/// An iterator position saved as offsets; see `IterCheckpoint::save`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IterState {
+    start: usize,
+    end: usize,
+}
+
+impl IterState {
+    /// Return the offset of the front of the iterator.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Return the offset of the back of the iterator.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Return the part of `data` in the saved range, or `None` if it
+    /// is out of bounds.
+    pub fn get<'a, T>(&self, data: &'a [T]) -> Option<&'a [T]> {
+        if self.end <= data.len() {
+            Some(&data[self.start..self.end])
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> IterCheckpoint<'a> for SliceIter<'a, T> {
+    type Elem = T;
+    fn checkpoint(&self) -> Checkpoint<'a, T> {
+        let slice = self.as_slice();
+        unsafe {
+            Checkpoint {
+                ptr: slice.as_ptr(),
+                end: slice.as_ptr().debug_add(slice.len()),
+                ty: PhantomData,
+            }
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: Checkpoint<'a, T>) {
+        unsafe {
+            *self = SliceIter::new(checkpoint.ptr, checkpoint.end);
+        }
+    }
+
+    fn resume(data: &'a [T], state: IterState) -> Option<Self> {
+        state.get(data).map(SliceIter::from)
+    }
+}
+
+impl<'a, T> IterCheckpoint<'a> for SliceCopyIter<'a, T> {
+    type Elem = T;
+    fn checkpoint(&self) -> Checkpoint<'a, T> {
+        Checkpoint {
+            ptr: self.ptr,
+            end: self.end,
+            ty: PhantomData,
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: Checkpoint<'a, T>) {
+        self.ptr = checkpoint.ptr;
+        self.end = checkpoint.end;
+    }
+
+    fn resume(data: &'a [T], state: IterState) -> Option<Self> {
+        state.get(data).map(|slice| {
+            unsafe {
+                let ptr = slice.as_ptr();
+                SliceCopyIter {
+                    ptr: ptr,
+                    end: ptr.debug_add(slice.len()),
+                    ty: PhantomData,
+                }
+            }
+        })
+    }
+}
+
+#[test]
+fn test_checkpoint() {
+    let data = [1, 2, 3, 4];
+    let mut iter = SliceCopyIter::from(&data[..]);
+    iter.next();
+    let cp = iter.checkpoint();
+    assert!(iter.by_ref().eq(vec![2, 3, 4]));
+    iter.rollback(cp);
+    assert_eq!(iter.next_back(), Some(4));
+    iter.rollback(cp);
+    assert_eq!(iter.len(), 3);
+
+    let other = SliceIter::from(&data[2..]);
+    let mut iter = SliceIter::from(&data[..]);
+    iter.rollback(other.checkpoint());
+    assert_eq!(iter.as_slice(), &[3, 4]);
+}
+
+#[test]
+fn test_save_resume() {
+    let data = [1, 2, 3, 4];
+    let mut iter = SliceCopyIter::from(&data[..]);
+    iter.next();
+    iter.next_back();
+    let state = iter.save(&data).unwrap();
+    assert_eq!((state.start(), state.end()), (1, 3));
+    assert!(iter.save(&data[2..]).is_none());
+    assert!(iter.save(&data[..2]).is_none());
+
+    let copy = data.to_vec();
+    let iter = SliceCopyIter::resume(&copy, state).unwrap();
+    assert!(iter.eq(vec![2, 3]));
+    assert!(SliceCopyIter::resume(&copy[..2], state).is_none());
+    assert!(SliceIter::resume(&copy[..3], state).is_some());
+}
+
+/// Split off the first or last element of a slice iterator, without
+/// changing the iterator itself.
+///
+/// This allows recursion over the elements in a persistent style.
+///
+/// ```
+/// use odds::slice::iter::{IterSplit, SliceIter};
+///
+/// fn sum(iter: SliceIter<i32>) -> i32 {
+///     match iter.split_first() {
+///         None => 0,
+///         Some((x, rest)) => x + sum(rest),
+///     }
+/// }
+///
+/// let data = [1, 2, 3];
+/// let iter = SliceIter::from(&data[..]);
+/// assert_eq!(sum(iter), 6);
+/// assert_eq!(iter.split_last().unwrap().0, &3);
+/// assert_eq!(iter.len(), 3);
+/// ```
+pub trait IterSplit<'a> : Sized {
+    type Elem: 'a;
+    /// Return the first element and an iterator of the rest, or `None` if
+    /// the iterator is empty.
+    fn split_first(&self) -> Option<(&'a Self::Elem, Self)>;
+
+    /// Return the last element and an iterator of the rest, or `None` if
+    /// the iterator is empty.
+    fn split_last(&self) -> Option<(&'a Self::Elem, Self)>;
+}
+
+impl<'a, T> IterSplit<'a> for SliceIter<'a, T> {
+    type Elem = T;
+    fn split_first(&self) -> Option<(&'a T, Self)> {
+        self.as_slice().split_first().map(|(x, rest)| (x, SliceIter::from(rest)))
+    }
+
+    fn split_last(&self) -> Option<(&'a T, Self)> {
+        self.as_slice().split_last().map(|(x, rest)| (x, SliceIter::from(rest)))
+    }
+}
+
+impl<'a, T> IterSplit<'a> for SliceCopyIter<'a, T> {
+    type Elem = T;
+    fn split_first(&self) -> Option<(&'a T, Self)> {
+        if self.ptr == self.end {
+            return None;
+        }
+        unsafe {
+            let rest = SliceCopyIter {
+                ptr: self.ptr.offset(1),
+                end: self.end,
+                ty: PhantomData,
+            };
+            Some((&*self.ptr, rest))
+        }
+    }
+
+    fn split_last(&self) -> Option<(&'a T, Self)> {
+        if self.ptr == self.end {
+            return None;
+        }
+        unsafe {
+            let rest = SliceCopyIter {
+                ptr: self.ptr,
+                end: self.end.offset(-1),
+                ty: PhantomData,
+            };
+            Some((&*rest.end, rest))
+        }
+    }
+}
+
+#[test]
+fn test_iter_split() {
+    let data = [1, 2, 3];
+    let iter = SliceCopyIter::from(&data[..]);
+    let (first, rest) = iter.split_first().unwrap();
+    assert_eq!(*first, 1);
+    assert_eq!(rest.as_slice(), &[2, 3]);
+    let (last, rest) = rest.split_last().unwrap();
+    assert_eq!(*last, 3);
+    assert_eq!(rest.as_slice(), &[2]);
+    let (_, rest) = rest.split_last().unwrap();
+    assert!(rest.split_first().is_none());
+    assert!(rest.split_last().is_none());
+    assert_eq!(iter.as_slice(), &data);
+
+    let iter = SliceIter::from(&data[..]);
+    let (first, rest) = iter.split_first().unwrap();
+    assert_eq!(*first, 1);
+    assert_eq!(rest.as_slice(), &[2, 3]);
+    assert_eq!(iter.as_slice(), &data);
+}
+
+/// An iterator adaptor that yields elements from a slice iterator while
+/// `pred` returns `true` for them.
+///
+/// Unlike `Iterator::take_while`, the first element that does not match
+/// is not consumed: the underlying iterator is left positioned at it.
+///
+/// ```
+/// use odds::slice::iter::{SliceIter, TakeWhileRef};
+///
+/// let input = b"123+45";
+/// let mut iter = SliceIter::from(&input[..]);
+/// let digits = TakeWhileRef::new(&mut iter, |c| c.is_ascii_digit()).count();
+/// assert_eq!(digits, 3);
+/// assert_eq!(iter.next(), Some(&b'+'));
+/// ```
+pub struct TakeWhileRef<'r, 'a, I: 'r, P> {
+    iter: &'r mut I,
+    pred: P,
+    ty: PhantomData<&'a ()>,
+}
+
+impl<'r, 'a, I, P> TakeWhileRef<'r, 'a, I, P>
+    where I: IterSplit<'a>,
+          P: FnMut(&I::Elem) -> bool
+{
+    /// Create a new `TakeWhileRef`.
+    pub fn new(iter: &'r mut I, pred: P) -> Self {
+        TakeWhileRef {
+            iter: iter,
+            pred: pred,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<'r, 'a, I, P> Iterator for TakeWhileRef<'r, 'a, I, P>
+    where I: IterSplit<'a>,
+          P: FnMut(&I::Elem) -> bool
+{
+    type Item = &'a I::Elem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.split_first() {
+            Some((elt, rest)) if (self.pred)(elt) => {
+                *self.iter = rest;
+                Some(elt)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'r, 'a, I, P> fmt::Debug for TakeWhileRef<'r, 'a, I, P>
+    where I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TakeWhileRef")
+         .field("iter", &self.iter)
+         .finish()
+    }
+}
+
+#[test]
+fn test_take_while_ref() {
+    let data = [1, 2, 3, 10, 4];
+    let mut iter = SliceCopyIter::from(&data[..]);
+    assert!(TakeWhileRef::new(&mut iter, |&x| x < 5).eq(&[1, 2, 3]));
+    assert_eq!(iter.as_slice(), &[10, 4]);
+    assert_eq!(TakeWhileRef::new(&mut iter, |&x| x < 5).next(), None);
+    assert_eq!(iter.next(), Some(10));
+    assert!(TakeWhileRef::new(&mut iter, |_| true).eq(&[4]));
+    assert_eq!(iter.next(), None);
+}
+
+/// An iterator adaptor that skips consecutive duplicate elements.
+///
+/// An element is a duplicate if `same(&first, &elt)` returns `true`, where
+/// `first` is the first element of the current run; only the first element
+/// of each run is yielded.
+///
+/// See [`.dedup()`](../trait.SliceIterExt.html#method.dedup) and
+/// [`.dedup_by()`](../trait.SliceIterExt.html#method.dedup_by) for more information.
+pub struct DedupIter<I, F>
+    where I: Iterator
+{
+    last: Option<I::Item>,
+    iter: I,
+    same: F,
+}
+
+impl<I: Clone, F: Clone> Clone for DedupIter<I, F>
+    where I: Iterator,
+          I::Item: Clone
+{
+    fn clone(&self) -> Self {
+        DedupIter {
+            last: self.last.clone(),
+            iter: self.iter.clone(),
+            same: self.same.clone(),
         }
     }
 }
 
+impl<I, F> DedupIter<I, F>
+    where I: Iterator,
+          F: FnMut(&I::Item, &I::Item) -> bool
+{
+    /// Create a new `DedupIter`.
+    pub fn new(mut iter: I, same: F) -> Self {
+        DedupIter {
+            last: iter.next(),
+            iter: iter,
+            same: same,
+        }
+    }
+}
+
+impl<I, F> Iterator for DedupIter<I, F>
+    where I: Iterator,
+          F: FnMut(&I::Item, &I::Item) -> bool
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        // this fuses the iterator
+        let last = match self.last.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        for next in &mut self.iter {
+            if !(self.same)(&last, &next) {
+                self.last = Some(next);
+                break;
+            }
+        }
+        Some(last)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = self.last.is_some() as usize;
+        let (_, hi) = self.iter.size_hint();
+        (extra, hi.and_then(|x| x.checked_add(extra)))
+    }
+}
+
+#[test]
+fn test_dedup() {
+    let data = [1, 1, 2, 3, 3, 3, 1];
+    let iter = DedupIter::new(SliceIter::from(&data[..]), |a, b| a == b);
+    assert_eq!(iter.size_hint(), (1, Some(7)));
+    assert!(iter.eq(&[1, 2, 3, 1]));
+
+    let iter = DedupIter::new(SliceCopyIter::from(&data[..]), |a, b| a == b);
+    assert!(iter.eq(vec![1, 2, 3, 1]));
+
+    let iter = DedupIter::new(SliceCopyIter::from(&data[..]), |a, b| a <= b);
+    assert!(iter.eq(vec![1]));
+
+    let empty: [i32; 0] = [];
+    let mut iter = DedupIter::new(SliceIter::from(&empty[..]), |a, b| a == b);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+}
+
+/// An iterator adaptor that alternates between the elements of two iterators.
+///
+/// The first element comes from `a`, then `b`, then `a` again, and so on.
+/// When one of the iterators is exhausted, the rest of the other one follows.
+///
+/// Iterating from the back requires both iterators to be exact size.
+///
+/// ```
+/// use odds::slice::iter::{Interleave, SliceCopyIter};
+///
+/// let left = [1, 3, 5];
+/// let right = [2, 4, 6, 8, 10];
+/// let mut iter = Interleave::new(SliceCopyIter::from(&left[..]),
+///                                SliceCopyIter::from(&right[..]));
+/// assert_eq!(iter.len(), 8);
+/// assert_eq!(iter.next_back(), Some(10));
+/// assert!(iter.eq(vec![1, 2, 3, 4, 5, 6, 8]));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Interleave<A, B> {
+    a: A,
+    b: B,
+    b_next: bool,
+}
+
+impl<A, B> Interleave<A, B>
+    where A: Iterator,
+          B: Iterator<Item=A::Item>,
+{
+    /// Create a new `Interleave` that starts with the first element of `a`.
+    pub fn new(a: A, b: B) -> Self {
+        Interleave {
+            a: a,
+            b: b,
+            b_next: false,
+        }
+    }
+
+    /// Return the two underlying iterators.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B> Iterator for Interleave<A, B>
+    where A: Iterator,
+          B: Iterator<Item=A::Item>,
+{
+    type Item = A::Item;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.b_next {
+            match self.b.next() {
+                None => self.a.next(),
+                elt => {
+                    self.b_next = false;
+                    elt
+                }
+            }
+        } else {
+            match self.a.next() {
+                None => self.b.next(),
+                elt => {
+                    self.b_next = true;
+                    elt
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (alow, ahi) = self.a.size_hint();
+        let (blow, bhi) = self.b.size_hint();
+        let hi = match (ahi, bhi) {
+            (Some(x), Some(y)) => x.checked_add(y),
+            _ => None,
+        };
+        (alow.saturating_add(blow), hi)
+    }
+}
+
+impl<A, B> DoubleEndedIterator for Interleave<A, B>
+    where A: DoubleEndedIterator + ExactSizeIterator,
+          B: DoubleEndedIterator<Item=A::Item> + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // The iterator that is not up next at the front yields the last
+        // element, unless the other one is strictly longer.
+        let alen = self.a.len();
+        let blen = self.b.len();
+        let a_last = if self.b_next { alen >= blen } else { alen > blen };
+        if a_last {
+            self.a.next_back()
+        } else {
+            self.b.next_back()
+        }
+    }
+}
+
+impl<A, B> ExactSizeIterator for Interleave<A, B>
+    where A: ExactSizeIterator,
+          B: ExactSizeIterator<Item=A::Item>,
+{ }
+
+#[test]
+fn test_interleave() {
+    let a = [1, 3, 5, 7, 9];
+    let b = [2, 4];
+    for i in 0..a.len() + 1 {
+        for j in 0..b.len() + 1 {
+            let iter = Interleave::new(SliceIter::from(&a[..i]), SliceIter::from(&b[..j]));
+            let forward = iter.collect::<Vec<_>>();
+            let mut backward = iter.rev().collect::<Vec<_>>();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            assert_eq!(forward.len(), iter.len());
+
+            // alternate between the front and the back
+            let mut iter = iter;
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            loop {
+                match iter.next() {
+                    None => break,
+                    Some(x) => front.push(x),
+                }
+                match iter.next_back() {
+                    None => break,
+                    Some(x) => back.push(x),
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(forward, front);
+        }
+    }
+    let iter = Interleave::new(SliceIter::from(&b[..]), SliceIter::from(&a[..]));
+    assert!(iter.eq(&[2, 1, 4, 3, 5, 7, 9]));
+}
+
+/// The position of an element yielded by `WithPosition`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The first of two or more elements
+    First,
+    /// Neither the first nor the last element
+    Middle,
+    /// The last of two or more elements
+    Last,
+    /// The only element
+    Only,
+}
+
+impl Position {
+    fn new(first: bool, last: bool) -> Self {
+        match (first, last) {
+            (true, true) => Position::Only,
+            (true, false) => Position::First,
+            (false, true) => Position::Last,
+            (false, false) => Position::Middle,
+        }
+    }
+}
+
+/// An iterator adaptor that yields each element together with its
+/// `Position` in the iteration.
+///
+/// The position is computed from the length of the underlying iterator,
+/// so no element needs to be peeked at.
+///
+/// See [`.with_position()`](../trait.SliceIterExt.html#method.with_position)
+/// for more information.
+#[derive(Copy, Clone, Debug)]
+pub struct WithPosition<I> {
+    iter: I,
+    front_started: bool,
+    back_started: bool,
+}
+
+impl<I> WithPosition<I>
+    where I: ExactSizeIterator
+{
+    /// Create a new `WithPosition`.
+    pub fn new(iter: I) -> Self {
+        WithPosition {
+            iter: iter,
+            front_started: false,
+            back_started: false,
+        }
+    }
+}
+
+impl<I> Iterator for WithPosition<I>
+    where I: ExactSizeIterator
+{
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elt = match self.iter.next() {
+            None => return None,
+            Some(x) => x,
+        };
+        let first = !self.front_started;
+        let last = self.iter.len() == 0 && !self.back_started;
+        self.front_started = true;
+        Some((Position::new(first, last), elt))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for WithPosition<I>
+    where I: ExactSizeIterator + DoubleEndedIterator
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elt = match self.iter.next_back() {
+            None => return None,
+            Some(x) => x,
+        };
+        let first = self.iter.len() == 0 && !self.front_started;
+        let last = !self.back_started;
+        self.back_started = true;
+        Some((Position::new(first, last), elt))
+    }
+}
+
+impl<I> ExactSizeIterator for WithPosition<I>
+    where I: ExactSizeIterator
+{ }
+
+#[test]
+fn test_with_position() {
+    use self::Position::*;
+
+    let data = [1, 2, 3, 4];
+    let iter = WithPosition::new(SliceCopyIter::from(&data[..]));
+    assert_eq!(iter.len(), 4);
+    assert!(iter.eq(vec![(First, 1), (Middle, 2), (Middle, 3), (Last, 4)]));
+
+    let mut iter = WithPosition::new(SliceCopyIter::from(&data[..]));
+    assert_eq!(iter.next_back(), Some((Last, 4)));
+    assert_eq!(iter.next(), Some((First, 1)));
+    assert_eq!(iter.next_back(), Some((Middle, 3)));
+    assert_eq!(iter.next(), Some((Middle, 2)));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = WithPosition::new(SliceIter::from(&data[..1]));
+    assert_eq!(iter.next_back(), Some((Only, &1)));
+    assert_eq!(iter.next(), None);
+
+    let mut iter = WithPosition::new(SliceCopyIter::from(&data[..2]));
+    assert_eq!(iter.next_back(), Some((Last, 2)));
+    assert_eq!(iter.next_back(), Some((First, 1)));
+}
+
+/// An iterator adaptor that keeps track of how many elements have been
+/// consumed from the underlying iterator, from either end.
+///
+/// The count is computed from the length of the underlying iterator, so
+/// for the pointer-based slice iterators it costs nothing in `next`.
+///
+/// See [`.counted()`](../trait.SliceIterExt.html#method.counted)
+/// for more information.
+#[derive(Copy, Clone, Debug)]
+pub struct Counted<I> {
+    iter: I,
+    start_len: usize,
+}
+
+impl<I> Counted<I>
+    where I: ExactSizeIterator
+{
+    /// Create a new `Counted`.
+    pub fn new(iter: I) -> Self {
+        Counted {
+            start_len: iter.len(),
+            iter: iter,
+        }
+    }
+
+    /// Return the number of elements consumed so far.
+    #[inline]
+    pub fn consumed(&self) -> usize {
+        self.start_len - self.iter.len()
+    }
+
+    /// Return the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Iterator for Counted<I>
+    where I: ExactSizeIterator
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for Counted<I>
+    where I: ExactSizeIterator + DoubleEndedIterator
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<I> ExactSizeIterator for Counted<I>
+    where I: ExactSizeIterator
+{ }
+
+#[test]
+fn test_counted() {
+    let data = [1, 2, 3, 4, 5];
+    let mut iter = Counted::new(SliceIter::from(&data[..]));
+    assert_eq!(iter.consumed(), 0);
+    iter.next();
+    iter.next();
+    assert_eq!(iter.consumed(), 2);
+    iter.next_back();
+    assert_eq!(iter.consumed(), 3);
+    assert_eq!(iter.len(), 2);
+    iter.by_ref().count();
+    assert_eq!(iter.consumed(), 5);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.consumed(), 5);
+}
+
+/// An iterator over the elements of a slice of slices, in order.
+///
+/// The outer slice may hold `&[T]`, `Vec<T>` or anything else that
+/// implements `AsRef<[T]>`. The current inner slice is iterated as a raw
+/// pointer range, and the outer slice is only touched when it is exhausted.
+///
+/// Iterator element type is `&'a T`.
+///
+/// ```
+/// use odds::slice::iter::FlatSliceIter;
+///
+/// let buffers = [&[1, 2][..], &[], &[3, 4, 5]];
+/// let iter = FlatSliceIter::new(&buffers);
+/// assert!(iter.eq(&[1, 2, 3, 4, 5]));
+///
+/// let buffers = vec![vec![1], vec![2, 3]];
+/// let sum: i32 = FlatSliceIter::new(&buffers).sum();
+/// assert_eq!(sum, 6);
+/// ```
+pub struct FlatSliceIter<'a, T: 'a, S: 'a = &'a [T]> {
+    ptr: *const T,
+    end: *const T,
+    outer: &'a [S],
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T, S> Copy for FlatSliceIter<'a, T, S> { }
+impl<'a, T, S> Clone for FlatSliceIter<'a, T, S> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T, S> FlatSliceIter<'a, T, S>
+    where S: AsRef<[T]>
+{
+    /// Create a new `FlatSliceIter` over the elements of each slice in
+    /// `data`.
+    ///
+    /// Panics if `T` is a zero-sized type. That case is not supported.
+    pub fn new(data: &'a [S]) -> Self {
+        assert!(size_of::<T>() != 0);
+        let ptr = align_of::<T>() as *const T;
+        FlatSliceIter {
+            ptr: ptr,
+            end: ptr,
+            outer: data,
+            ty: PhantomData,
+        }
+    }
+
+    /// Return the rest of the current inner slice.
+    pub fn inner_remainder(&self) -> &'a [T] {
+        unsafe {
+            from_raw_parts(self.ptr, self.ptr.distance_to(self.end))
+        }
+    }
+
+    /// Return the inner slices that have not been started yet.
+    pub fn outer_remainder(&self) -> &'a [S] {
+        self.outer
+    }
+
+    /// Move on to the next inner slice, and return `false` if there is none.
+    #[cold]
+    fn next_inner(&mut self) -> bool {
+        match self.outer.split_first() {
+            None => false,
+            Some((first, rest)) => {
+                let inner = first.as_ref();
+                self.outer = rest;
+                self.ptr = inner.as_ptr();
+                unsafe {
+                    self.end = self.ptr.debug_add(inner.len());
+                }
+                true
+            }
+        }
+    }
+}
+
+impl<'a, T, S> Iterator for FlatSliceIter<'a, T, S>
+    where S: AsRef<[T]>
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        while self.ptr == self.end {
+            if !self.next_inner() {
+                return None;
+            }
+        }
+        unsafe {
+            let elt = &*self.ptr;
+            self.ptr = self.ptr.offset(1);
+            Some(elt)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ptr.distance_to(self.end);
+        if self.outer.is_empty() {
+            (len, Some(len))
+        } else {
+            (len, None)
+        }
+    }
+
+    fn fold<Acc, G>(self, init: Acc, mut g: G) -> Acc
+        where G: FnMut(Acc, &'a T) -> Acc
+    {
+        let mut acc = self.inner_remainder().iter().fold(init, &mut g);
+        for inner in self.outer {
+            acc = inner.as_ref().iter().fold(acc, &mut g);
+        }
+        acc
+    }
+}
+
+impl<'a, T, S> fmt::Debug for FlatSliceIter<'a, T, S>
+    where T: fmt::Debug,
+          S: AsRef<[T]> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FlatSliceIter")
+         .field("inner", &self.inner_remainder())
+         .field("outer", &self.outer)
+         .finish()
+    }
+}
+
+#[test]
+fn test_flat_slice_iter() {
+    let empty: [&[i32]; 0] = [];
+    assert_eq!(FlatSliceIter::new(&empty).next(), None);
+
+    let data = [&[][..], &[1], &[], &[], &[2, 3], &[]];
+    let mut iter = FlatSliceIter::new(&data);
+    assert_eq!(iter.size_hint(), (0, None));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.inner_remainder(), &[3]);
+    assert_eq!(iter.outer_remainder().len(), 1);
+    assert_eq!(iter.size_hint(), (1, None));
+    assert_eq!(iter.clone().fold(0, |a, &b| a + b), 3);
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    let data = vec![vec![1, 2], vec![], vec![3]];
+    let iter = FlatSliceIter::new(&data);
+    assert_eq!(iter.fold(Vec::new(), |mut v, &x| { v.push(x); v }), vec![1, 2, 3]);
+}
+
+/// An iterator adaptor that yields every pair `(a, b)` of elements from
+/// two iterators, with `a` in the outer loop and `b` in the inner loop.
+///
+/// The iterator `b` is cloned once for each element of `a`, so it should
+/// be cheap to clone, like `SliceCopyIter`.
+///
+/// Its size hint is exact if both iterators' are, as long as the number of
+/// pairs fits in `usize`. It does not implement `ExactSizeIterator`, since
+/// the number of pairs can overflow.
+///
+/// ```
+/// use odds::slice::iter::{Product, SliceCopyIter};
+///
+/// let xs = [1, 2];
+/// let ys = ['a', 'b', 'c'];
+/// let iter = Product::new(SliceCopyIter::from(&xs[..]), SliceCopyIter::from(&ys[..]));
+/// assert_eq!(iter.size_hint(), (6, Some(6)));
+/// assert!(iter.eq(vec![(1, 'a'), (1, 'b'), (1, 'c'),
+///                      (2, 'a'), (2, 'b'), (2, 'c')]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Product<A, B>
+    where A: Iterator
+{
+    a: A,
+    a_cur: Option<A::Item>,
+    b: B,
+    b_orig: B,
+}
+
+impl<A, B> Product<A, B>
+    where A: Iterator,
+          A::Item: Clone,
+          B: Iterator + Clone,
+{
+    /// Create a new `Product`.
+    pub fn new(a: A, b: B) -> Self {
+        Product {
+            a: a,
+            a_cur: None,
+            b: b.clone(),
+            b_orig: b,
+        }
+    }
+}
+
+impl<A, B> Iterator for Product<A, B>
+    where A: Iterator,
+          A::Item: Clone,
+          B: Iterator + Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref x) = self.a_cur {
+                if let Some(y) = self.b.next() {
+                    return Some((x.clone(), y));
+                }
+            }
+            match self.a.next() {
+                None => {
+                    self.a_cur = None;
+                    return None;
+                }
+                Some(x) => {
+                    self.a_cur = Some(x);
+                    self.b = self.b_orig.clone();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.size_hint();
+        let (orig_lo, orig_hi) = self.b_orig.size_hint();
+        let (cur_lo, cur_hi) = if self.a_cur.is_some() {
+            self.b.size_hint()
+        } else {
+            (0, Some(0))
+        };
+        let lo = a_lo.saturating_mul(orig_lo).saturating_add(cur_lo);
+        let hi = match (a_hi, orig_hi, cur_hi) {
+            (Some(a), Some(orig), Some(cur)) => {
+                a.checked_mul(orig).and_then(|x| x.checked_add(cur))
+            }
+            _ => None,
+        };
+        (lo, hi)
+    }
+}
+
+#[test]
+fn test_product() {
+    let data = [1, 2, 3];
+    let empty: [i32; 0] = [];
+    let mut iter = Product::new(SliceCopyIter::from(&data[..]), SliceCopyIter::from(&data[1..]));
+    assert_eq!(iter.size_hint(), (6, Some(6)));
+    assert_eq!(iter.next(), Some((1, 2)));
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.next(), Some((1, 3)));
+    assert_eq!(iter.next(), Some((2, 2)));
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.count(), 3);
+
+    let mut iter = Product::new(SliceCopyIter::from(&data[..]), SliceCopyIter::from(&empty[..]));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    let mut iter = Product::new(SliceCopyIter::from(&empty[..]), SliceCopyIter::from(&data[..]));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+
+    let iter = Product::new(0..usize::max_value(), 0..3);
+    assert_eq!(iter.size_hint(), (usize::max_value(), None));
+}
+
+/// An iterator over the elements of two slices, one after the other.
+///
+/// It holds both slices as raw pointer ranges, and switches over to the
+/// second one exactly once, when the first one is exhausted.
+///
+/// Iterator element type is `&'a T`.
+///
+/// ```
+/// use odds::slice::iter::ChainSlices;
+///
+/// let header = [0xff, 2];
+/// let body = [10, 20];
+/// let mut iter = ChainSlices::new(&header, &body);
+/// assert_eq!(iter.len(), 4);
+/// assert_eq!(iter.next_back(), Some(&20));
+/// assert!(iter.eq(&[0xff, 2, 10]));
+/// ```
+pub struct ChainSlices<'a, T: 'a> {
+    ptr: *const T,
+    end: *const T,
+    second_ptr: *const T,
+    second_end: *const T,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T> Copy for ChainSlices<'a, T> { }
+impl<'a, T> Clone for ChainSlices<'a, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T> ChainSlices<'a, T> {
+    /// Create a new `ChainSlices` over the elements of `a` and then `b`.
+    ///
+    /// Panics if `T` is a zero-sized type. That case is not supported.
+    pub fn new(a: &'a [T], b: &'a [T]) -> Self {
+        assert!(size_of::<T>() != 0);
+        unsafe {
+            ChainSlices {
+                ptr: a.as_ptr(),
+                end: a.as_ptr().debug_add(a.len()),
+                second_ptr: b.as_ptr(),
+                second_end: b.as_ptr().debug_add(b.len()),
+                ty: PhantomData,
+            }
+        }
+    }
+
+    /// Return the remaining elements, as two slices.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        unsafe {
+            (from_raw_parts(self.ptr, self.ptr.distance_to(self.end)),
+             from_raw_parts(self.second_ptr, self.second_ptr.distance_to(self.second_end)))
+        }
+    }
+
+    /// Move the second range into the first, and leave the second empty.
+    #[cold]
+    fn switch(&mut self) {
+        self.ptr = self.second_ptr;
+        self.end = self.second_end;
+        self.second_ptr = self.second_end;
+    }
+}
+
+impl<'a, T> Iterator for ChainSlices<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.ptr == self.end {
+            if self.second_ptr == self.second_end {
+                return None;
+            }
+            self.switch();
+        }
+        unsafe {
+            let elt = &*self.ptr;
+            self.ptr = self.ptr.offset(1);
+            Some(elt)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ptr.distance_to(self.end) +
+                  self.second_ptr.distance_to(self.second_end);
+        (len, Some(len))
+    }
+
+    fn fold<Acc, G>(self, init: Acc, mut g: G) -> Acc
+        where G: FnMut(Acc, &'a T) -> Acc
+    {
+        let (a, b) = self.as_slices();
+        let acc = a.iter().fold(init, &mut g);
+        b.iter().fold(acc, &mut g)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChainSlices<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        unsafe {
+            if self.second_ptr != self.second_end {
+                self.second_end = self.second_end.offset(-1);
+                Some(&*self.second_end)
+            } else if self.ptr != self.end {
+                self.end = self.end.offset(-1);
+                Some(&*self.end)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ChainSlices<'a, T> { }
+
+impl<'a, T> fmt::Debug for ChainSlices<'a, T>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b) = self.as_slices();
+        f.debug_tuple("ChainSlices")
+         .field(&a)
+         .field(&b)
+         .finish()
+    }
+}
+
+#[test]
+fn test_chain_slices() {
+    let data = [1, 2, 3, 4, 5];
+    for i in 0..data.len() + 1 {
+        let (a, b) = data.split_at(i);
+        assert!(ChainSlices::new(a, b).eq(&data));
+        assert!(ChainSlices::new(a, b).rev().eq(data.iter().rev()));
+        assert_eq!(ChainSlices::new(a, b).fold(0, |acc, &x| acc * 10 + x), 12345);
+    }
+
+    let mut iter = ChainSlices::new(&data[..2], &data[2..]);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.as_slices(), (&[][..], &[3, 4, 5][..]));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.as_slices(), (&[4, 5][..], &[][..]));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(&4));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+/// An iterator adaptor that allows up to `N` elements to be put back, to be
+/// yielded again before the rest of the underlying iterator.
+///
+/// The elements put back are yielded in reverse order, last in first out.
+///
+/// ```
+/// use odds::slice::iter::{PutBack, SliceCopyIter};
+///
+/// let data = b"ab";
+/// let mut iter: PutBack<_> = PutBack::new(SliceCopyIter::from(&data[..]));
+/// let c = iter.next().unwrap();
+/// assert_eq!(iter.put_back(c), Ok(()));
+/// assert_eq!(iter.put_back(b'x'), Err(b'x'));
+/// assert!(iter.eq(b"ab".iter().cloned()));
+/// ```
+pub struct PutBack<I, const N: usize = 1>
+    where I: Iterator
+{
+    buf: [MaybeUninit<I::Item>; N],
+    len: usize,
+    iter: I,
+}
+
+impl<I, const N: usize> Clone for PutBack<I, N>
+    where I: Iterator + Clone,
+          I::Item: Copy
+{
+    fn clone(&self) -> Self {
+        PutBack {
+            buf: self.buf,
+            len: self.len,
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<I, const N: usize> PutBack<I, N>
+    where I: Iterator,
+          I::Item: Copy
+{
+    /// Create a new `PutBack` with room for `N` elements.
+    pub fn new(iter: I) -> Self {
+        PutBack {
+            buf: [MaybeUninit::uninit(); N],
+            len: 0,
+            iter: iter,
+        }
+    }
+
+    /// Put back `elt`, to be yielded by the next call to `next`.
+    ///
+    /// Return `Err(elt)` if there is no room for it.
+    pub fn put_back(&mut self, elt: I::Item) -> Result<(), I::Item> {
+        if self.len == N {
+            return Err(elt);
+        }
+        self.buf[self.len] = MaybeUninit::new(elt);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Return the elements that are put back, in the order they were put
+    /// back, and the underlying iterator.
+    pub fn into_parts(self) -> (PutBackBuf<I::Item, N>, I) {
+        let buf = PutBackBuf {
+            buf: self.buf,
+            len: self.len,
+        };
+        (buf, self.iter)
+    }
+}
+
+impl<I, const N: usize> Iterator for PutBack<I, N>
+    where I: Iterator,
+          I::Item: Copy
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.len != 0 {
+            self.len -= 1;
+            unsafe {
+                Some(self.buf[self.len].assume_init())
+            }
+        } else {
+            self.iter.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        (lo.saturating_add(self.len), hi.and_then(|x| x.checked_add(self.len)))
+    }
+}
+
+impl<I, const N: usize> ExactSizeIterator for PutBack<I, N>
+    where I: ExactSizeIterator,
+          I::Item: Copy
+{ }
+
+impl<I, const N: usize> fmt::Debug for PutBack<I, N>
+    where I: Iterator + fmt::Debug,
+          I::Item: Copy + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let buf = PutBackBuf { buf: self.buf, len: self.len };
+        f.debug_struct("PutBack")
+         .field("buf", &buf.as_slice())
+         .field("iter", &self.iter)
+         .finish()
+    }
+}
+
+/// The elements that were put back into a `PutBack`.
+pub struct PutBackBuf<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T: Copy, const N: usize> Copy for PutBackBuf<T, N> { }
+impl<T: Copy, const N: usize> Clone for PutBackBuf<T, N> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T, const N: usize> PutBackBuf<T, N>
+    where T: Copy
+{
+    /// Return the elements, in the order they were put back.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            from_raw_parts(self.buf.as_ptr() as *const T, self.len)
+        }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for PutBackBuf<T, N>
+    where T: Copy + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+#[test]
+fn test_put_back() {
+    let data = [1, 2, 3];
+    let mut iter = PutBack::<_, 2>::new(SliceCopyIter::from(&data[..]));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.put_back(10), Ok(()));
+    assert_eq!(iter.put_back(20), Ok(()));
+    assert_eq!(iter.put_back(30), Err(30));
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(20));
+    assert_eq!(iter.put_back(40), Ok(()));
+    let (buf, rest) = iter.into_parts();
+    assert_eq!(buf.as_slice(), &[10, 40]);
+    assert_eq!(rest.as_slice(), &[2, 3]);
+
+    let mut iter: PutBack<_> = PutBack::new(SliceCopyIter::from(&data[..0]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.put_back(5), Ok(()));
+    assert_eq!(iter.next(), Some(5));
+    assert_eq!(iter.next(), None);
+}
+
+/// An iterator adaptor over a `SliceIter` that allows looking any number of
+/// elements ahead without consuming them.
+///
+/// Each call to `.peek()` looks one element further ahead; the peek
+/// position is reset by `.reset_peek()` and by each call to `.next()`.
+///
+/// ```
+/// use odds::slice::iter::{MultiPeek, SliceIter};
+///
+/// let tokens = ["if", "(", "x", ")"];
+/// let mut iter = MultiPeek::new(SliceIter::from(&tokens[..]));
+/// assert_eq!(iter.peek(), Some(&"if"));
+/// assert_eq!(iter.peek(), Some(&"("));
+/// assert_eq!(iter.peek_nth(3), Some(&")"));
+/// iter.reset_peek();
+/// assert_eq!(iter.peek(), Some(&"if"));
+/// assert_eq!(iter.next(), Some(&"if"));
+/// assert_eq!(iter.peek(), Some(&"("));
+/// ```
+#[derive(Copy, Clone)]
+pub struct MultiPeek<'a, T: 'a> {
+    iter: SliceIter<'a, T>,
+    index: usize,
+}
+
+impl<'a, T> MultiPeek<'a, T> {
+    /// Create a new `MultiPeek`.
+    pub fn new(iter: SliceIter<'a, T>) -> Self {
+        MultiPeek {
+            iter: iter,
+            index: 0,
+        }
+    }
+
+    /// Return the element after the ones already peeked at, and move the
+    /// peek position forward; or `None` if there are no more elements.
+    pub fn peek(&mut self) -> Option<&'a T> {
+        let elt = self.iter.as_slice().get(self.index);
+        if elt.is_some() {
+            self.index += 1;
+        }
+        elt
+    }
+
+    /// Return the element `n` places ahead, where `0` is the next element,
+    /// without moving the peek position.
+    pub fn peek_nth(&self, n: usize) -> Option<&'a T> {
+        self.iter.as_slice().get(n)
+    }
+
+    /// Reset the peek position to the next element.
+    pub fn reset_peek(&mut self) {
+        self.index = 0;
+    }
+
+    /// Return the remaining elements as a slice.
+    pub fn as_slice(&self) -> &'a [T] {
+        self.iter.as_slice()
+    }
+
+    /// Return the underlying iterator.
+    pub fn into_inner(self) -> SliceIter<'a, T> {
+        self.iter
+    }
+}
+
+impl<'a, T> Iterator for MultiPeek<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.index = 0;
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for MultiPeek<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        let elt = self.iter.next_back();
+        self.index = min(self.index, self.iter.len());
+        elt
+    }
+}
+
+impl<'a, T> ExactSizeIterator for MultiPeek<'a, T> { }
+
+impl<'a, T> fmt::Debug for MultiPeek<'a, T>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MultiPeek")
+         .field("remainder", &self.as_slice())
+         .field("index", &self.index)
+         .finish()
+    }
+}
+
+#[test]
+fn test_multi_peek() {
+    let data = [1, 2, 3];
+    let mut iter = MultiPeek::new(SliceIter::from(&data[..]));
+    assert_eq!(iter.peek(), Some(&1));
+    assert_eq!(iter.peek(), Some(&2));
+    assert_eq!(iter.peek(), Some(&3));
+    assert_eq!(iter.peek(), None);
+    assert_eq!(iter.peek(), None);
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.peek(), None);
+    iter.reset_peek();
+    assert_eq!(iter.peek(), Some(&1));
+    assert_eq!(iter.peek(), Some(&2));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.peek_nth(0), Some(&2));
+    assert_eq!(iter.peek_nth(1), None);
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.peek(), None);
+}
+
+/// An endless iterator over a slice, that goes back to the start after it
+/// reaches the end.
+///
+/// Iterator element type is `&'a T`.
+///
+/// ```
+/// use odds::slice::iter::CircularIter;
+///
+/// let pattern = [1, 0, 0];
+/// let mut iter = CircularIter::new(&pattern);
+/// assert_eq!(iter.nth(4), Some(&0));
+/// assert_eq!(iter.index(), 2);
+/// assert!(iter.take(4).eq(&[0, 1, 0, 0]));
+/// ```
+pub struct CircularIter<'a, T: 'a> {
+    start: *const T,
+    ptr: *const T,
+    end: *const T,
+    ty: PhantomData<&'a T>,
+}
+
+impl<'a, T> Copy for CircularIter<'a, T> { }
+impl<'a, T> Clone for CircularIter<'a, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T> CircularIter<'a, T> {
+    /// Create a new `CircularIter`, starting at the first element of `data`.
+    ///
+    /// **Panics** if `data` is empty, or if `T` is a zero-sized type.
+    pub fn new(data: &'a [T]) -> Self {
+        assert!(!data.is_empty());
+        assert!(size_of::<T>() != 0);
+        unsafe {
+            let ptr = data.as_ptr();
+            CircularIter {
+                start: ptr,
+                ptr: ptr,
+                end: ptr.debug_add(data.len()),
+                ty: PhantomData,
+            }
+        }
+    }
+
+    /// Return the index in the slice of the next element.
+    pub fn index(&self) -> usize {
+        self.start.distance_to(self.ptr)
+    }
+
+    /// Return the whole slice.
+    pub fn as_slice(&self) -> &'a [T] {
+        unsafe {
+            from_raw_parts(self.start, self.start.distance_to(self.end))
+        }
+    }
+}
+
+impl<'a, T> Iterator for CircularIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        unsafe {
+            let elt = &*self.ptr;
+            self.ptr = self.ptr.offset(1);
+            if self.ptr == self.end {
+                self.ptr = self.start;
+            }
+            Some(elt)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::max_value(), None)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        let len = self.start.distance_to(self.end);
+        let index = self.index();
+        // compute (index + n) % len without overflow
+        let index = (index + n % len) % len;
+        unsafe {
+            self.ptr = self.start.debug_add(index);
+        }
+        self.next()
+    }
+}
+
+impl<'a, T> fmt::Debug for CircularIter<'a, T>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CircularIter")
+         .field("data", &self.as_slice())
+         .field("index", &self.index())
+         .finish()
+    }
+}
+
+#[test]
+fn test_circular_iter() {
+    let data = [1, 2, 3];
+    let iter = CircularIter::new(&data);
+    assert!(iter.take(7).eq(&[1, 2, 3, 1, 2, 3, 1]));
+
+    let mut iter = CircularIter::new(&data);
+    assert_eq!(iter.nth(2), Some(&3));
+    assert_eq!(iter.index(), 0);
+    assert_eq!(iter.nth(usize::max_value()), Some(&1));
+    assert_eq!(iter.index(), 1);
+    for n in 0..10 {
+        let mut a = CircularIter::new(&data[1..]);
+        let mut b = a;
+        assert_eq!(a.nth(n), b.by_ref().skip(n).next());
+        assert_eq!(a.index(), b.index());
+    }
+
+    let mut iter = CircularIter::new(&data[..1]);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.nth(5), Some(&1));
+}
+
+/// An iterator that merges the elements of two sorted slice iterators, in
+/// sorted order.
+///
+/// The merge is stable: of two equal elements, the one from the first
+/// iterator comes first.
+///
+/// Iterator element type is `&'a T`.
+///
+/// ```
+/// use odds::slice::iter::{MergeSorted, SliceIter};
+///
+/// let a = [1, 4, 5];
+/// let b = [2, 3, 6, 7];
+/// let iter = MergeSorted::new(SliceIter::from(&a[..]), SliceIter::from(&b[..]));
+/// assert_eq!(iter.len(), 7);
+/// assert!(iter.eq(&[1, 2, 3, 4, 5, 6, 7]));
+///
+/// // merge descending sequences
+/// let a = [9, 3];
+/// let b = [8, 3, 2];
+/// let iter = MergeSorted::by(SliceIter::from(&a[..]), SliceIter::from(&b[..]),
+///                            |x, y| x > y);
+/// assert!(iter.eq(&[9, 8, 3, 3, 2]));
+/// ```
+pub struct MergeSorted<'a, T: 'a, F = fn(&T, &T) -> bool> {
+    a: &'a [T],
+    b: &'a [T],
+    less_than: F,
+}
+
+impl<'a, T> MergeSorted<'a, T>
+    where T: Ord
+{
+    /// Create a new `MergeSorted` for two iterators sorted in ascending
+    /// order.
+    pub fn new(a: SliceIter<'a, T>, b: SliceIter<'a, T>) -> Self {
+        MergeSorted::by(a, b, PartialOrd::lt)
+    }
+}
+
+impl<'a, T, F> MergeSorted<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    /// Create a new `MergeSorted` for two iterators sorted by the
+    /// `less_than` ordering.
+    pub fn by(a: SliceIter<'a, T>, b: SliceIter<'a, T>, less_than: F) -> Self {
+        MergeSorted {
+            a: a.as_slice(),
+            b: b.as_slice(),
+            less_than: less_than,
+        }
+    }
+
+    /// Return the remaining elements of both iterators.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.a, self.b)
+    }
+}
+
+impl<'a, T, F> Iterator for MergeSorted<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let take_b = match (self.a.first(), self.b.first()) {
+            (Some(x), Some(y)) => (self.less_than)(y, x),
+            (None, Some(_)) => true,
+            (_, None) => false,
+        };
+        let side = if take_b { &mut self.b } else { &mut self.a };
+        match side.split_first() {
+            None => None,
+            Some((elt, rest)) => {
+                *side = rest;
+                Some(elt)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, F> DoubleEndedIterator for MergeSorted<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        let take_a = match (self.a.last(), self.b.last()) {
+            (Some(x), Some(y)) => (self.less_than)(y, x),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        let side = if take_a { &mut self.a } else { &mut self.b };
+        match side.split_last() {
+            None => None,
+            Some((elt, rest)) => {
+                *side = rest;
+                Some(elt)
+            }
+        }
+    }
+}
+
+impl<'a, T, F> ExactSizeIterator for MergeSorted<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{ }
+
+impl<'a, T, F: Clone> Clone for MergeSorted<'a, T, F> {
+    fn clone(&self) -> Self {
+        MergeSorted {
+            a: self.a,
+            b: self.b,
+            less_than: self.less_than.clone(),
+        }
+    }
+}
+
+impl<'a, T, F> fmt::Debug for MergeSorted<'a, T, F>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MergeSorted")
+         .field("a", &self.a)
+         .field("b", &self.b)
+         .finish()
+    }
+}
+
+#[test]
+fn test_merge_sorted() {
+    let a = [(1, 'a'), (2, 'a'), (2, 'a'), (5, 'a')];
+    let b = [(0, 'b'), (2, 'b'), (5, 'b'), (6, 'b')];
+    for i in 0..a.len() + 1 {
+        for j in 0..b.len() + 1 {
+            let mut expected = a[..i].iter().chain(&b[..j]).collect::<Vec<_>>();
+            expected.sort_by_key(|x| x.0);
+            let iter = MergeSorted::by(SliceIter::from(&a[..i]), SliceIter::from(&b[..j]),
+                                       |x, y| x.0 < y.0);
+            assert_eq!(iter.len(), i + j);
+            assert!(iter.clone().eq(expected.iter().cloned()));
+            assert!(iter.rev().eq(expected.iter().cloned().rev()));
+        }
+    }
+
+    let mut iter = MergeSorted::new(SliceIter::from(&a[..]), SliceIter::from(&b[..]));
+    assert_eq!(iter.next(), Some(&(0, 'b')));
+    assert_eq!(iter.next_back(), Some(&(6, 'b')));
+    assert_eq!(iter.as_slices(), (&a[..], &b[1..3]));
+}
+
+/// An iterator that merges the elements of many sorted slices, in sorted
+/// order.
+///
+/// The merge uses a binary heap of one cursor per slice, and is stable: of
+/// equal elements, the ones from earlier slices come first.
+///
+/// Iterator element type is `&'a T`.
+///
+/// Requires `feature="std"`
+///
+/// ```
+/// use odds::slice::iter::KMerge;
+///
+/// let runs = [&[1, 5, 9][..], &[2, 3], &[], &[4, 6, 7, 8]];
+/// let iter = KMerge::new(&runs);
+/// assert_eq!(iter.len(), 9);
+/// assert!(iter.eq(&[1, 2, 3, 4, 5, 6, 7, 8, 9]));
+/// ```
+#[cfg(feature = "std")]
+pub struct KMerge<'a, T: 'a, F = fn(&T, &T) -> bool> {
+    heap: Vec<(usize, SliceIter<'a, T>)>,
+    len: usize,
+    less_than: F,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> KMerge<'a, T>
+    where T: Ord
+{
+    /// Create a new `KMerge` for slices sorted in ascending order.
+    ///
+    /// The elements of `inputs` can be `&[T]`, `Vec<T>` or anything else
+    /// that implements `AsRef<[T]>`.
+    pub fn new<S>(inputs: &'a [S]) -> Self
+        where S: AsRef<[T]>
+    {
+        KMerge::by(inputs, PartialOrd::lt)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, F> KMerge<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    /// Create a new `KMerge` for slices sorted by the `less_than`
+    /// ordering.
+    pub fn by<S>(inputs: &'a [S], less_than: F) -> Self
+        where S: AsRef<[T]>
+    {
+        let mut heap = Vec::with_capacity(inputs.len());
+        let mut len = 0;
+        for (i, input) in inputs.iter().enumerate() {
+            let input = input.as_ref();
+            if !input.is_empty() {
+                len += input.len();
+                heap.push((i, SliceIter::from(input)));
+            }
+        }
+        let mut merge = KMerge {
+            heap: heap,
+            len: len,
+            less_than: less_than,
+        };
+        for i in (0..merge.heap.len() / 2).rev() {
+            merge.sift_down(i);
+        }
+        merge
+    }
+
+    /// Return `true` if the cursor at heap index `i` should come before the
+    /// one at `j`. Both cursors must be non-empty.
+    fn before(&mut self, i: usize, j: usize) -> bool {
+        let (ref a_index, ref a) = self.heap[i];
+        let (ref b_index, ref b) = self.heap[j];
+        let (x, y) = (&a.as_slice()[0], &b.as_slice()[0]);
+        (self.less_than)(x, y) || (!(self.less_than)(y, x) && a_index < b_index)
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.heap.len();
+        loop {
+            let left = 2 * i + 1;
+            if left >= n {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < n && self.before(right, left) { right } else { left };
+            if !self.before(child, i) {
+                break;
+            }
+            self.heap.swap(i, child);
+            i = child;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, F> Iterator for KMerge<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let elt = match self.heap.first_mut() {
+            None => return None,
+            Some(&mut (_, ref mut cursor)) => cursor.next(),
+        };
+        if self.heap[0].1.len() == 0 {
+            self.heap.swap_remove(0);
+        }
+        self.sift_down(0);
+        self.len -= 1;
+        elt
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, F> ExactSizeIterator for KMerge<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{ }
+
+#[cfg(feature = "std")]
+impl<'a, T, F> fmt::Debug for KMerge<'a, T, F>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cursors = self.heap.iter().map(|c| c.1.as_slice()).collect::<Vec<_>>();
+        f.debug_struct("KMerge")
+         .field("cursors", &cursors)
+         .finish()
+    }
+}
+
+#[test]
+fn test_kmerge() {
+    let runs = vec![
+        vec![(0, 'a'), (3, 'a'), (3, 'a'), (8, 'a')],
+        vec![],
+        vec![(3, 'c'), (4, 'c')],
+        vec![(1, 'd'), (3, 'd'), (9, 'd')],
+        vec![(0, 'e')],
+    ];
+    let mut expected = runs.iter().flat_map(|v| v).collect::<Vec<_>>();
+    expected.sort_by_key(|x| x.0);
+    let iter = KMerge::by(&runs, |x, y| x.0 < y.0);
+    assert_eq!(iter.len(), expected.len());
+    assert!(iter.eq(expected));
+
+    let empty: [&[i32]; 0] = [];
+    assert_eq!(KMerge::new(&empty).next(), None);
+    let runs = [&[2, 1][..], &[]];
+    let mut iter = KMerge::new(&runs);
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.len(), 1);
+}
+
+/// An iterator over `n` contiguous subslices whose lengths differ by at most
+/// one; the longer ones come first.
+///
+/// Iterator element type is `&'a [T]`.
+///
+/// See [`.split_n()`](../trait.SliceExt.html#tymethod.split_n) for more
+/// information.
+pub struct BalancedChunks<'a, T: 'a> {
+    data: &'a [T],
+    chunks: usize,
+}
+
+impl<'a, T> Copy for BalancedChunks<'a, T> { }
+impl<'a, T> Clone for BalancedChunks<'a, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T> BalancedChunks<'a, T> {
+    /// Create a new `BalancedChunks`, splitting `data` into `n` pieces.
+    ///
+    /// **Panics** if `n` is zero.
+    pub fn new(data: &'a [T], n: usize) -> Self {
+        assert!(n != 0, "BalancedChunks: number of chunks must not be zero");
+        BalancedChunks {
+            data: data,
+            chunks: n,
+        }
+    }
+
+    /// Return the part of the slice that is not yet iterated.
+    pub fn as_slice(&self) -> &'a [T] {
+        self.data
+    }
+}
+
+impl<'a, T> Iterator for BalancedChunks<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.chunks == 0 {
+            return None;
+        }
+        // round up, so that the longer chunks come first
+        let len = (self.data.len() + self.chunks - 1) / self.chunks;
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        self.chunks -= 1;
+        Some(head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.chunks, Some(self.chunks))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for BalancedChunks<'a, T> {
+    fn next_back(&mut self) -> Option<&'a [T]> {
+        if self.chunks == 0 {
+            return None;
+        }
+        let len = self.data.len() / self.chunks;
+        let (head, tail) = self.data.split_at(self.data.len() - len);
+        self.data = head;
+        self.chunks -= 1;
+        Some(tail)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for BalancedChunks<'a, T> { }
+
+impl<'a, T> fmt::Debug for BalancedChunks<'a, T>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BalancedChunks")
+         .field("data", &self.data)
+         .field("chunks", &self.chunks)
+         .finish()
+    }
+}
+
+/// An iterator over `n` contiguous mutable subslices whose lengths differ by
+/// at most one; the longer ones come first.
+///
+/// Iterator element type is `&'a mut [T]`.
+///
+/// See [`.split_n_mut()`](../trait.SliceExt.html#tymethod.split_n_mut) for
+/// more information.
+pub struct BalancedChunksMut<'a, T: 'a> {
+    data: &'a mut [T],
+    chunks: usize,
+}
+
+impl<'a, T> BalancedChunksMut<'a, T> {
+    /// Create a new `BalancedChunksMut`, splitting `data` into `n` pieces.
+    ///
+    /// **Panics** if `n` is zero.
+    pub fn new(data: &'a mut [T], n: usize) -> Self {
+        assert!(n != 0, "BalancedChunksMut: number of chunks must not be zero");
+        BalancedChunksMut {
+            data: data,
+            chunks: n,
+        }
+    }
+
+    /// Return the part of the slice that is not yet iterated.
+    pub fn into_slice(self) -> &'a mut [T] {
+        self.data
+    }
+}
+
+impl<'a, T> Iterator for BalancedChunksMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<&'a mut [T]> {
+        if self.chunks == 0 {
+            return None;
+        }
+        let len = (self.data.len() + self.chunks - 1) / self.chunks;
+        let data = mem::replace(&mut self.data, &mut []);
+        let (head, tail) = data.split_at_mut(len);
+        self.data = tail;
+        self.chunks -= 1;
+        Some(head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.chunks, Some(self.chunks))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for BalancedChunksMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut [T]> {
+        if self.chunks == 0 {
+            return None;
+        }
+        let len = self.data.len() / self.chunks;
+        let data = mem::replace(&mut self.data, &mut []);
+        let split = data.len() - len;
+        let (head, tail) = data.split_at_mut(split);
+        self.data = head;
+        self.chunks -= 1;
+        Some(tail)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for BalancedChunksMut<'a, T> { }
+
+impl<'a, T> fmt::Debug for BalancedChunksMut<'a, T>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BalancedChunksMut")
+         .field("data", &self.data)
+         .field("chunks", &self.chunks)
+         .finish()
+    }
+}
+
+#[test]
+fn test_balanced_chunks() {
+    let data = (0..10).collect::<Vec<_>>();
+    for n in 1..14 {
+        let iter = BalancedChunks::new(&data, n);
+        assert_eq!(iter.len(), n);
+        let lens = iter.map(|s| s.len()).collect::<Vec<_>>();
+        assert_eq!(lens.iter().sum::<usize>(), data.len());
+        assert!(lens.windows(2).all(|w| w[0] >= w[1] && w[0] - w[1] <= 1));
+        assert!(iter.flat_map(|s| s).eq(&data));
+
+        let mut back = iter.rev().collect::<Vec<_>>();
+        back.reverse();
+        assert_eq!(back, iter.collect::<Vec<_>>());
+    }
+    let mut iter = BalancedChunks::new(&data, 3);
+    assert_eq!(iter.next_back(), Some(&data[7..]));
+    assert_eq!(iter.next(), Some(&data[..4]));
+    assert_eq!(iter.as_slice(), &data[4..7]);
+
+    let mut data = [0; 7];
+    for (i, chunk) in BalancedChunksMut::new(&mut data, 3).rev().enumerate() {
+        for x in chunk {
+            *x = i;
+        }
+    }
+    assert_eq!(data, [2, 2, 2, 1, 1, 0, 0]);
+}
@@ -0,0 +1,357 @@
+//! Line splitting for byte buffers
+
+use std::cmp::min;
+use std::ops::Range;
+
+use super::{find_byte, find_byte2};
+
+/// Options for splitting byte buffers into lines.
+///
+/// By default, line terminators are stripped and quotes are not
+/// special.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineOptions {
+    keep_terminator: bool,
+    quote: Option<u8>,
+}
+
+impl LineOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        LineOptions::default()
+    }
+
+    /// Set whether the `\n` or `\r\n` line terminator is kept at the end
+    /// of each line.
+    pub fn keep_terminator(mut self, keep: bool) -> Self {
+        self.keep_terminator = keep;
+        self
+    }
+
+    /// Set the quote byte: line terminators between a pair of quotes don't
+    /// end the line. A doubled quote inside a quoted section acts as an
+    /// escaped quote.
+    pub fn quote(mut self, quote: Option<u8>) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    fn finish_line<'a>(&self, line: &'a [u8]) -> &'a [u8] {
+        if self.keep_terminator {
+            return line;
+        }
+        let mut line = line;
+        if line.last() == Some(&b'\n') {
+            line = &line[..line.len() - 1];
+            if line.last() == Some(&b'\r') {
+                line = &line[..line.len() - 1];
+            }
+        }
+        line
+    }
+
+    /// Return the index of the `\n` that ends the first line of `data`.
+    ///
+    /// `in_quote` is the quoting state at the start of `data`, and is updated
+    /// to the state after the `\n`, or at the end of `data` if there is none.
+    fn find_end(&self, data: &[u8], in_quote: &mut bool) -> Option<usize> {
+        let quote = match self.quote {
+            None => return find_byte(data, b'\n'),
+            Some(q) => q,
+        };
+        let mut offset = 0;
+        loop {
+            let rest = &data[offset..];
+            let pos = if *in_quote {
+                find_byte(rest, quote)
+            } else {
+                find_byte2(rest, b'\n', quote)
+            };
+            match pos {
+                None => return None,
+                Some(i) if rest[i] == b'\n' => return Some(offset + i),
+                Some(i) => {
+                    *in_quote = !*in_quote;
+                    offset += i + 1;
+                }
+            }
+        }
+    }
+}
+
+/// Return an iterator of the lines of `data`, split on `\n` or `\r\n`,
+/// with the terminators stripped.
+///
+/// See `ByteLines::with_options` for more configuration.
+///
+/// ```
+/// use odds::slice::lines::split_lines_bytes;
+///
+/// let data = b"first\r\nsecond\n\nlast";
+/// let lines = split_lines_bytes(data).collect::<Vec<_>>();
+/// assert_eq!(lines, vec![&b"first"[..], b"second", b"", b"last"]);
+/// ```
+pub fn split_lines_bytes(data: &[u8]) -> ByteLines {
+    ByteLines::with_options(data, LineOptions::new())
+}
+
+/// An iterator of the lines of a byte slice.
+///
+/// A final line without a terminator is yielded too, unless it is empty.
+#[derive(Clone, Debug)]
+pub struct ByteLines<'a> {
+    rest: &'a [u8],
+    options: LineOptions,
+}
+
+impl<'a> ByteLines<'a> {
+    /// Create an iterator of the lines of `data`, split according to
+    /// `options`.
+    ///
+    /// ```
+    /// use odds::slice::lines::{ByteLines, LineOptions};
+    ///
+    /// let data = b"a,\"b\nc\"\nd\n";
+    /// let options = LineOptions::new().keep_terminator(true).quote(Some(b'"'));
+    /// let lines = ByteLines::with_options(data, options).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec![&b"a,\"b\nc\"\n"[..], b"d\n"]);
+    /// ```
+    pub fn with_options(data: &'a [u8], options: LineOptions) -> Self {
+        ByteLines {
+            rest: data,
+            options: options,
+        }
+    }
+
+    /// Return the part of the data that has not been split yet.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for ByteLines<'a> {
+    type Item = &'a [u8];
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let mut in_quote = false;
+        let line = match self.options.find_end(self.rest, &mut in_quote) {
+            Some(i) => &self.rest[..i + 1],
+            None => self.rest,
+        };
+        self.rest = &self.rest[line.len()..];
+        Some(self.options.finish_line(line))
+    }
+}
+
+/// Splits a stream of byte chunks into lines, carrying any incomplete
+/// line over from one chunk to the next.
+///
+/// Requires `feature="std"`
+///
+/// ```
+/// use odds::slice::lines::{LineOptions, StreamLines};
+///
+/// let mut lines = Vec::new();
+/// let mut stream = StreamLines::new(LineOptions::new());
+/// for chunk in &[&b"one\ntw"[..], b"o\r", b"\nthree"] {
+///     stream.feed(chunk, |line| lines.push(line.to_vec()));
+/// }
+/// stream.finish(|line| lines.push(line.to_vec()));
+/// assert_eq!(lines, vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct StreamLines {
+    partial: Vec<u8>,
+    in_quote: bool,
+    options: LineOptions,
+}
+
+#[cfg(feature = "std")]
+impl StreamLines {
+    /// Create a new, empty, `StreamLines`.
+    pub fn new(options: LineOptions) -> Self {
+        StreamLines {
+            partial: Vec::new(),
+            in_quote: false,
+            options: options,
+        }
+    }
+
+    /// Return the incomplete line carried over from the previous chunks.
+    pub fn partial(&self) -> &[u8] {
+        &self.partial
+    }
+
+    /// Split `chunk` into lines, and call `f` with each complete line.
+    ///
+    /// Lines that are wholly inside `chunk` are passed on without copying.
+    pub fn feed<F>(&mut self, chunk: &[u8], mut f: F)
+        where F: FnMut(&[u8])
+    {
+        let mut chunk = chunk;
+        while let Some(i) = self.options.find_end(chunk, &mut self.in_quote) {
+            let (line, rest) = chunk.split_at(i + 1);
+            if self.partial.is_empty() {
+                f(self.options.finish_line(line));
+            } else {
+                self.partial.extend_from_slice(line);
+                f(self.options.finish_line(&self.partial));
+                self.partial.clear();
+            }
+            self.in_quote = false;
+            chunk = rest;
+        }
+        self.partial.extend_from_slice(chunk);
+    }
+
+    /// End the stream, calling `f` with the final line if it is not empty.
+    pub fn finish<F>(&mut self, mut f: F)
+        where F: FnMut(&[u8])
+    {
+        if !self.partial.is_empty() {
+            f(self.options.finish_line(&self.partial));
+            self.partial.clear();
+        }
+        self.in_quote = false;
+    }
+}
+
+/// Split `buf` into consecutive ranges of about `approx_chunk` bytes each,
+/// moving each split point forward to a record boundary.
+///
+/// For each tentative split point, `boundary` is called with the rest of
+/// the buffer from there, and returns the offset of the next boundary in
+/// it, or `None` if there is none, in which case the last range extends to
+/// the end of the buffer. The ranges are never empty and cover all of
+/// `buf`.
+///
+/// This is useful to divide a big buffer among threads without cutting
+/// any record in half.
+///
+/// **Panics** if `approx_chunk` is zero.
+///
+/// ```
+/// use odds::slice::lines::align_chunk_boundaries;
+///
+/// let data = b"aa\nbbbb\nc\nddd\n";
+/// let after_newline = |rest: &[u8]| rest.iter().position(|&b| b == b'\n').map(|i| i + 1);
+/// let ranges = align_chunk_boundaries(data, 4, after_newline).collect::<Vec<_>>();
+/// assert_eq!(ranges, vec![0..8, 8..14]);
+/// assert_eq!(&data[ranges[0].clone()], b"aa\nbbbb\n");
+/// ```
+pub fn align_chunk_boundaries<'a, F>(buf: &'a [u8], approx_chunk: usize, boundary: F)
+    -> ChunkBoundaries<'a, F>
+    where F: Fn(&[u8]) -> Option<usize>
+{
+    assert!(approx_chunk != 0);
+    ChunkBoundaries {
+        buf: buf,
+        start: 0,
+        approx_chunk: approx_chunk,
+        boundary: boundary,
+    }
+}
+
+/// An iterator of the ranges of a byte buffer, split at record boundaries.
+///
+/// See [`align_chunk_boundaries()`](fn.align_chunk_boundaries.html) for
+/// more information.
+#[derive(Clone, Debug)]
+pub struct ChunkBoundaries<'a, F> {
+    buf: &'a [u8],
+    start: usize,
+    approx_chunk: usize,
+    boundary: F,
+}
+
+impl<'a, F> Iterator for ChunkBoundaries<'a, F>
+    where F: Fn(&[u8]) -> Option<usize>
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let len = self.buf.len();
+        let start = self.start;
+        if start == len {
+            return None;
+        }
+        let end = if len - start <= self.approx_chunk {
+            len
+        } else {
+            let split = start + self.approx_chunk;
+            match (self.boundary)(&self.buf[split..]) {
+                Some(offset) => min(split.saturating_add(offset), len),
+                None => len,
+            }
+        };
+        self.start = end;
+        Some(start..end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.buf.len() - self.start;
+        let max = rest / self.approx_chunk + (rest % self.approx_chunk != 0) as usize;
+        ((rest != 0) as usize, Some(max))
+    }
+}
+
+#[test]
+fn test_byte_lines() {
+    let data = b"\n\r\na\r\r\nb";
+    let lines = split_lines_bytes(data).collect::<Vec<_>>();
+    assert_eq!(lines, vec![&b""[..], b"", b"a\r", b"b"]);
+
+    let options = LineOptions::new().keep_terminator(true);
+    let lines = ByteLines::with_options(data, options).collect::<Vec<_>>();
+    assert_eq!(lines, vec![&b"\n"[..], b"\r\n", b"a\r\r\n", b"b"]);
+    assert_eq!(lines.concat(), &data[..]);
+
+    let options = LineOptions::new().quote(Some(b'\''));
+    let data = b"'x\n''\ny'\nz\n'";
+    let lines = ByteLines::with_options(data, options).collect::<Vec<_>>();
+    assert_eq!(lines, vec![&b"'x\n''\ny'"[..], b"z", b"'"]);
+
+    assert_eq!(split_lines_bytes(b"").next(), None);
+}
+
+#[test]
+fn test_stream_lines() {
+    let data = b"ab\r\n'c\nd'\r\n\ne'f";
+    let options = LineOptions::new().quote(Some(b'\''));
+    let expected = ByteLines::with_options(data, options).collect::<Vec<_>>();
+    for chunk_size in 1..data.len() + 1 {
+        let mut lines = Vec::new();
+        let mut stream = StreamLines::new(options);
+        for chunk in data.chunks(chunk_size) {
+            stream.feed(chunk, |line| lines.push(line.to_vec()));
+        }
+        stream.finish(|line| lines.push(line.to_vec()));
+        assert_eq!(lines, expected);
+        assert_eq!(stream.partial(), b"");
+    }
+}
+
+#[test]
+fn test_align_chunk_boundaries() {
+    let data = b"one\ntwo\nthree\nfour";
+    let after_newline = |rest: &[u8]| find_byte(rest, b'\n').map(|i| i + 1);
+    for approx in 1..data.len() + 2 {
+        let ranges = align_chunk_boundaries(data, approx, after_newline).collect::<Vec<_>>();
+        let mut start = 0;
+        for r in &ranges {
+            assert_eq!(r.start, start);
+            assert!(r.end > r.start);
+            assert!(r.end == data.len() || data[r.end - 1] == b'\n');
+            start = r.end;
+        }
+        assert_eq!(start, data.len());
+    }
+    let mut iter = align_chunk_boundaries(data, 5, |_| None);
+    assert_eq!(iter.size_hint(), (1, Some(4)));
+    assert_eq!(iter.next(), Some(0..data.len()));
+    assert_eq!(iter.next(), None);
+    assert_eq!(align_chunk_boundaries(b"", 5, |_| Some(0)).next(), None);
+}
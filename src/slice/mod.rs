@@ -1,21 +1,41 @@
 //! Extra functions for slices
 
+pub mod arena;
 pub mod blocked;
+pub mod finder;
+pub mod group;
+pub mod heap;
 pub mod iter;
+pub mod lines;
 pub mod unalign;
 pub mod rev;
+pub mod scan;
+pub mod sort;
 
-use {slice_unchecked};
+use {slice_unchecked, slice_unchecked_mut, IndexRange};
+use pointer::PointerExt;
+use random::RandomSource;
 
+pub use self::arena::SliceArena;
 pub use self::rev::RevSlice;
 
+use self::finder::Finder;
+use self::iter::{BalancedChunks, BalancedChunksMut, Counted, DedupIter, WithPosition};
+use self::sort::sort_insertion_by;
+
 use std::ptr;
-use std::cmp::min;
-use std::mem::{self, align_of, size_of};
-use std::slice::from_raw_parts;
+use std::cmp::{min, Ordering};
+use std::mem::{self, align_of, size_of, MaybeUninit};
+use std::ops::Range;
+use std::slice::{from_raw_parts, from_raw_parts_mut};
 
 use rawslice::SliceIter;
 
+/// The low bit of each byte of a u64
+const BYTES_LO: u64 = 0x0101010101010101;
+/// The high bit of each byte of a u64
+const BYTES_HI: u64 = 0x8080808080808080;
+
 /// Unaligned load of a u64 at index `i` in `buf`
 unsafe fn load_u64(buf: &[u8], i: usize) -> u64 {
     debug_assert!(i + 8 <= buf.len());
@@ -24,6 +44,12 @@ unsafe fn load_u64(buf: &[u8], i: usize) -> u64 {
     data
 }
 
+/// Unaligned store of `data` at index `i` in `buf`
+unsafe fn store_u64(buf: &mut [u8], i: usize, data: u64) {
+    debug_assert!(i + 8 <= buf.len());
+    ptr::copy_nonoverlapping(&data as *const _ as *const u8, buf.get_unchecked_mut(i), 8);
+}
+
 /// Return the end index of the longest shared (equal) prefix of `a` and `b`.
 pub fn shared_prefix(a: &[u8], b: &[u8]) -> usize {
     let len = min(a.len(), b.len());
@@ -38,178 +64,2493 @@ pub fn shared_prefix(a: &[u8], b: &[u8]) -> usize {
             let b1 = load_u64(b, 8);
             let d0 = a0 ^ b0;
             let d1 = a1 ^ b1;
-            if d0 ^ d1 != 0 {
+            // combine with `|`: with `^`, words that differ in the same
+            // way would cancel out and the difference would be missed
+            if d0 | d1 != 0 {
+                break;
+            }
+        }
+        offset += 16;
+        a = &a[16..];
+        b = &b[16..];
+    }
+    for i in 0..a.len() {
+        if a[i] != b[i] {
+            return offset + i;
+        }
+    }
+    len
+}
+
+/// Return the length of the longest shared (equal) suffix of `a` and `b`.
+pub fn shared_suffix(a: &[u8], b: &[u8]) -> usize {
+    let len = min(a.len(), b.len());
+    let mut a = &a[a.len() - len..];
+    let mut b = &b[b.len() - len..];
+    let mut offset = 0;
+    while a.len() >= 16 {
+        let n = a.len();
+        unsafe {
+            let a0 = load_u64(a, n - 16);
+            let a1 = load_u64(a, n - 8);
+            let b0 = load_u64(b, n - 16);
+            let b1 = load_u64(b, n - 8);
+            if (a0 ^ b0) | (a1 ^ b1) != 0 {
                 break;
             }
         }
-        offset += 16;
-        a = &a[16..];
-        b = &b[16..];
+        offset += 16;
+        a = &a[..n - 16];
+        b = &b[..n - 16];
+    }
+    for i in (0..a.len()).rev() {
+        if a[i] != b[i] {
+            return offset + a.len() - 1 - i;
+        }
+    }
+    len
+}
+
+/// Compare `a` and `b` lexicographically, like `Ord` for `[u8]`.
+///
+/// Compares a word at a time; the first word that differs decides the
+/// order, so single bytes are only compared in the tail.
+///
+/// ```
+/// use odds::slice::compare_bytes;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(compare_bytes(b"apple pie", b"apple tart"), Ordering::Less);
+/// assert_eq!(compare_bytes(b"apple", b"apple pie"), Ordering::Less);
+/// assert_eq!(compare_bytes(b"pear", b"pear"), Ordering::Equal);
+/// ```
+pub fn compare_bytes(a: &[u8], b: &[u8]) -> Ordering {
+    let len = min(a.len(), b.len());
+    let mut i = 0;
+    while i + 8 <= len {
+        unsafe {
+            // big endian, so that integer order is lexicographic order
+            let x = u64::from_be(load_u64(a, i));
+            let y = u64::from_be(load_u64(b, i));
+            if x != y {
+                return x.cmp(&y);
+            }
+        }
+        i += 8;
+    }
+    while i < len {
+        if a[i] != b[i] {
+            return a[i].cmp(&b[i]);
+        }
+        i += 1;
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Return the length of the longest common prefix of `a` and `b`.
+///
+/// For byte slices, `shared_prefix` is faster, comparing a word at a time.
+///
+/// ```
+/// use odds::slice::{common_prefix_len, common_suffix_len};
+///
+/// let a = ["usr", "lib", "rustlib"];
+/// let b = ["usr", "lib", "python"];
+/// assert_eq!(common_prefix_len(&a, &b), 2);
+/// assert_eq!(common_suffix_len(&a, &b), 0);
+/// ```
+pub fn common_prefix_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    a.iter().zip(b).take_while(|&(x, y)| x == y).count()
+}
+
+/// Return the length of the longest common suffix of `a` and `b`.
+///
+/// For byte slices, `shared_suffix` is faster, comparing a word at a time.
+pub fn common_suffix_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    a.iter().rev().zip(b.iter().rev()).take_while(|&(x, y)| x == y).count()
+}
+
+/// Return the index of the first occurrence of `needle` in `haystack`, or
+/// `None` if there is none. An empty needle is found at index 0.
+///
+/// See `find_subslice_bytes` for a faster version for byte slices.
+///
+/// ```
+/// use odds::slice::{find_subslice, rfind_subslice};
+///
+/// let data = [1, 2, 3, 1, 2, 3];
+/// assert_eq!(find_subslice(&data, &[2, 3]), Some(1));
+/// assert_eq!(rfind_subslice(&data, &[2, 3]), Some(4));
+/// assert_eq!(find_subslice(&data, &[3, 2]), None);
+/// ```
+pub fn find_subslice<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    (0..haystack.len() - needle.len() + 1)
+        .position(|i| &haystack[i..i + needle.len()] == needle)
+}
+
+/// Return the index of the last occurrence of `needle` in `haystack`, or
+/// `None` if there is none. An empty needle is found at the end.
+pub fn rfind_subslice<T: PartialEq>(haystack: &[T], needle: &[T]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    (0..haystack.len() - needle.len() + 1)
+        .rposition(|i| &haystack[i..i + needle.len()] == needle)
+}
+
+/// Return the index of the first occurrence of `needle` in `haystack`, or
+/// `None` if there is none. An empty needle is found at index 0.
+///
+/// This searches with a `finder::Finder`, which takes linear time in the
+/// worst case. To search for the same needle many times, create the
+/// `Finder` once and reuse it.
+///
+/// ```
+/// use odds::slice::find_subslice_bytes;
+///
+/// let frame = b"\x00\x01payload\r\n\r\nnext";
+/// assert_eq!(find_subslice_bytes(frame, b"\r\n\r\n"), Some(9));
+/// ```
+pub fn find_subslice_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    Finder::new(needle).find(haystack)
+}
+
+#[test]
+fn test_find_subslice() {
+    let haystack = b"abracadabra, abracadabra! cadabra";
+    let needles: &[&[u8]] = &[b"", b"a", b"abra", b"cad", b"ra!", b"dabra",
+                              b"cadabra", b"x", b"bra, a", b"abracadabra! cadabrax"];
+    for start in 0..haystack.len() + 1 {
+        let h = &haystack[start..];
+        for &needle in needles {
+            let naive = if needle.is_empty() {
+                Some(0)
+            } else {
+                h.windows(needle.len()).position(|w| w == needle)
+            };
+            assert_eq!(find_subslice(h, needle), naive);
+            assert_eq!(find_subslice_bytes(h, needle), naive);
+            let rnaive = if needle.is_empty() {
+                Some(h.len())
+            } else {
+                h.windows(needle.len()).rposition(|w| w == needle)
+            };
+            assert_eq!(rfind_subslice(h, needle), rnaive);
+        }
+    }
+    let mut zeros = [0u8; 100];
+    zeros[77] = 1;
+    assert_eq!(find_subslice_bytes(&zeros, &[0, 1, 0]), Some(76));
+    assert_eq!(find_subslice_bytes(&zeros, &[1, 0, 0, 0]), Some(77));
+    assert_eq!(find_subslice_bytes(&zeros, &[0, 0, 1]), Some(75));
+    // a needle that matches at every position except in its middle
+    let mut needle = [0u8; 20];
+    needle[10] = 1;
+    assert_eq!(find_subslice_bytes(&[0; 100], &needle), None);
+}
+
+/// Rotate `steps` towards lower indices.
+///
+/// The steps to rotate is computed modulo the length of `data`,
+/// so any step value is acceptable. This function does not panic.
+///
+/// ```
+/// use odds::slice::rotate_left;
+///
+/// let mut data = [1, 2, 3, 4];
+/// rotate_left(&mut data, 1);
+/// assert_eq!(&data, &[2, 3, 4, 1]);
+/// rotate_left(&mut data, 2);
+/// assert_eq!(&data, &[4, 1, 2, 3]);
+/// ```
+pub fn rotate_left<T>(data: &mut [T], steps: usize) {
+    //return rotate_alt(data, steps);
+    // no bounds checks in this method in this version
+    if data.len() == 0 {
+        return;
+    }
+    let steps = steps % data.len();
+
+    data[..steps].reverse();
+    data[steps..].reverse();
+    data.reverse();
+}
+
+/// Rotate `steps` towards higher indices.
+///
+/// The steps to rotate is computed modulo the length of `data`,
+/// so any step value is acceptable. This function does not panic.
+///
+/// ```
+/// use odds::slice::rotate_right;
+///
+/// let mut data = [1, 2, 3, 4];
+/// rotate_right(&mut data, 1);
+/// assert_eq!(&data, &[4, 1, 2, 3]);
+/// ```
+pub fn rotate_right<T>(data: &mut [T], steps: usize) {
+    if data.len() == 0 {
+        return;
+    }
+    let steps = steps % data.len();
+    rotate_left(data, data.len() - steps);
+}
+
+/// Swap the contents of `a` and `b`.
+///
+/// **Panics** if the slices have different lengths.
+///
+/// ```
+/// use odds::slice::swap_ranges;
+///
+/// let mut front = [1, 2, 3];
+/// let mut back = [4, 5, 6];
+/// swap_ranges(&mut front, &mut back);
+/// assert_eq!(front, [4, 5, 6]);
+/// assert_eq!(back, [1, 2, 3]);
+///
+/// // swap the halves of one slice
+/// let mut data = [1, 2, 3, 4];
+/// let (x, y) = data.split_at_mut(2);
+/// swap_ranges(x, y);
+/// assert_eq!(data, [3, 4, 1, 2]);
+/// ```
+pub fn swap_ranges<T>(a: &mut [T], b: &mut [T]) {
+    assert_eq!(a.len(), b.len(), "swap_ranges: slices have different lengths");
+    // two mutable slices can't overlap
+    unsafe {
+        ptr::swap_nonoverlapping(a.as_mut_ptr(), b.as_mut_ptr(), a.len());
+    }
+}
+
+/// Return `true` if `a` and `b` are equal, in time that depends only on
+/// their lengths and not on their contents.
+///
+/// Use this to compare secrets like authentication tokens or MACs, where an
+/// early exit at the first difference would leak timing information. The
+/// lengths are not secret: slices of different lengths are unequal right
+/// away.
+///
+/// ```
+/// use odds::slice::constant_time_eq;
+///
+/// assert!(constant_time_eq(b"secret-token", b"secret-token"));
+/// assert!(!constant_time_eq(b"secret-token", b"secret-tokeN"));
+/// assert!(!constant_time_eq(b"secret", b"secret-token"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // accumulate through volatile reads and writes, which the optimizer
+    // must perform one by one, so that it can't turn the loop into an
+    // early exit
+    let mut diff = 0u8;
+    for i in 0..a.len() {
+        unsafe {
+            let acc = ptr::read_volatile(&diff);
+            ptr::write_volatile(&mut diff, acc | (a[i] ^ b[i]));
+        }
+    }
+    unsafe { ptr::read_volatile(&diff) == 0 }
+}
+
+/// Rotate the bytes of `data` `steps` towards lower indices.
+///
+/// Like `rotate_left`, but when the shorter side of the rotation is small,
+/// it is moved through a buffer on the stack so that the rest of the data
+/// is moved with a single `memmove`.
+///
+/// ```
+/// use odds::slice::rotate_left_bytes;
+///
+/// let mut data = *b"worldhello ";
+/// rotate_left_bytes(&mut data, 5);
+/// assert_eq!(&data, b"hello world");
+/// ```
+pub fn rotate_left_bytes(data: &mut [u8], steps: usize) {
+    const BUF: usize = 256;
+    let len = data.len();
+    if len == 0 {
+        return;
+    }
+    let steps = steps % len;
+    let mut buf = [0u8; BUF];
+    let ptr = data.as_mut_ptr();
+    unsafe {
+        if steps <= BUF && steps <= len - steps {
+            // move the front part out, shift the rest down, put it back at the end
+            ptr::copy_nonoverlapping(ptr, buf.as_mut_ptr(), steps);
+            ptr::copy(ptr.debug_add(steps), ptr, len - steps);
+            ptr::copy_nonoverlapping(buf.as_ptr(), ptr.debug_add(len - steps), steps);
+        } else if len - steps <= BUF {
+            // move the back part out, shift the rest up, put it back at the start
+            let back = len - steps;
+            ptr::copy_nonoverlapping(ptr.debug_add(steps), buf.as_mut_ptr(), back);
+            ptr::copy(ptr, ptr.debug_add(back), steps);
+            ptr::copy_nonoverlapping(buf.as_ptr(), ptr, back);
+        } else {
+            rotate_left(data, steps);
+        }
+    }
+}
+
+#[test]
+fn test_rotate() {
+    for &len in &[0, 1, 2, 7, 300, 600] {
+        let data = (0..len).map(|x| x as u8).collect::<Vec<_>>();
+        for &steps in &[0, 1, 3, 255, 256, 257, 299, 344, 599, 1000] {
+            let k = if len == 0 { 0 } else { steps % len };
+            let mut expected = data[k..].to_vec();
+            expected.extend_from_slice(&data[..k]);
+
+            let mut v = data.clone();
+            rotate_left(&mut v, steps);
+            assert_eq!(v, expected);
+            let mut v = data.clone();
+            rotate_left_bytes(&mut v, steps);
+            assert_eq!(v, expected);
+            rotate_right(&mut v, steps);
+            assert_eq!(v, data);
+        }
+    }
+}
+
+/// Call `f` with each pair of adjacent elements of `data`, front to back,
+/// with both elements mutable.
+///
+/// Each call's borrows end before the next call, so the element that is
+/// second in one pair can be first in the next.
+///
+/// ```
+/// use odds::slice::for_each_pair_mut;
+///
+/// // running sum, in place
+/// let mut data = [1, 2, 3, 4];
+/// for_each_pair_mut(&mut data, |a, b| *b += *a);
+/// assert_eq!(data, [1, 3, 6, 10]);
+/// ```
+pub fn for_each_pair_mut<T, F>(data: &mut [T], mut f: F)
+    where F: FnMut(&mut T, &mut T)
+{
+    let len = data.len();
+    if len < 2 {
+        return;
+    }
+    let ptr = data.as_mut_ptr();
+    unsafe {
+        for i in 0..len - 1 {
+            let a = ptr.debug_add(i);
+            f(&mut *a, &mut *a.offset(1));
+        }
+    }
+}
+
+#[test]
+fn test_shared_suffix() {
+    let mut a = [0xff; 100];
+    let b = [0xff; 120];
+    for i in 0..a.len() {
+        a[i] = 0;
+        let expected = a.len() - 1 - i;
+        assert_eq!(shared_suffix(&a, &b), expected);
+        assert_eq!(shared_suffix(&b, &a), expected);
+        assert_eq!(common_suffix_len(&a, &b), expected);
+        assert_eq!(shared_prefix(&a, &b), i);
+        assert_eq!(common_prefix_len(&a, &b), i);
+        a[i] = 0xff;
+    }
+    assert_eq!(shared_suffix(&a, &b), a.len());
+    assert_eq!(shared_suffix(&a, &[]), 0);
+}
+
+#[test]
+fn test_shared_prefix() {
+    let mut a = [0xff; 256];
+    let b = [0xff; 256];
+    for byte in 0..255 { // don't test byte 255
+        for i in 0..a.len() {
+            a[i] = byte;
+            let ans = shared_prefix(&a, &b);
+            assert!(ans == i, "failed for index {} and byte {:x} (got ans={})",
+                    i, byte, ans);
+            a[i] = 0xff;
+        }
+    }
+    // both words differ in the same way
+    let mut a = [0u8; 16];
+    a[3] = 1;
+    a[11] = 1;
+    assert_eq!(shared_prefix(&a, &[0; 16]), 3);
+}
+
+/// Binary search the abstract index space `0..len`.
+///
+/// `f(i)` must return the ordering of the element at index `i` relative to
+/// the target, and the elements must be sorted so that `f` returns `Less`,
+/// then `Equal`, then `Greater` as `i` increases.
+///
+/// Return `Ok(i)` for an index where `f(i)` is `Equal`, or `Err(i)` with
+/// the index where an element equal to the target could be inserted.
+///
+/// ```
+/// use odds::slice::binary_search_range;
+///
+/// // search the squares without storing them
+/// assert_eq!(binary_search_range(100, |i| (i * i).cmp(&49)), Ok(7));
+/// assert_eq!(binary_search_range(100, |i| (i * i).cmp(&50)), Err(8));
+/// assert_eq!(binary_search_range(0, |_| unreachable!()), Err(0));
+/// ```
+pub fn binary_search_range<F>(len: usize, mut f: F) -> Result<usize, usize>
+    where F: FnMut(usize) -> Ordering
+{
+    let mut low = 0;
+    let mut high = len;
+    while low < high {
+        // can't overflow, unlike (low + high) / 2
+        let mid = low + (high - low) / 2;
+        match f(mid) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+    Err(low)
+}
+
+#[test]
+fn test_binary_search_range() {
+    let data = [1, 3, 3, 5, 8, 13];
+    for x in 0..15 {
+        let res = binary_search_range(data.len(), |i| data[i].cmp(&x));
+        match data.binary_search(&x) {
+            Ok(_) => assert_eq!(data[res.unwrap()], x),
+            Err(i) => assert_eq!(res, Err(i)),
+        }
+    }
+    let big = !0;
+    assert_eq!(binary_search_range(big, |i| i.cmp(&(big - 1))), Ok(big - 1));
+}
+
+/// Fill `data` with elements pulled from `iter`, front to back.
+///
+/// Return the number of elements written, which is less than `data.len()`
+/// if the iterator ran out first. No more elements than needed are pulled
+/// from the iterator.
+///
+/// ```
+/// use odds::slice::fill_slice;
+///
+/// let mut iter = 1..6;
+/// let mut buf = [0; 3];
+/// assert_eq!(fill_slice(&mut iter, &mut buf), 3);
+/// assert_eq!(buf, [1, 2, 3]);
+/// assert_eq!(fill_slice(&mut iter, &mut buf), 2);
+/// assert_eq!(buf, [4, 5, 3]);
+/// ```
+pub fn fill_slice<I>(iter: I, data: &mut [I::Item]) -> usize
+    where I: IntoIterator,
+{
+    let mut iter = iter.into_iter();
+    for (i, slot) in data.iter_mut().enumerate() {
+        match iter.next() {
+            Some(elt) => *slot = elt,
+            None => return i,
+        }
+    }
+    data.len()
+}
+
+/// Drops the first `len` elements of `data` when dropped.
+struct PartialArrayGuard<'a, T: 'a> {
+    data: &'a mut [MaybeUninit<T>],
+    len: usize,
+}
+
+impl<'a, T> Drop for PartialArrayGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            let init = &mut self.data[..self.len] as *mut [MaybeUninit<T>] as *mut [T];
+            ptr::drop_in_place(init);
+        }
+    }
+}
+
+/// Pull exactly `N` elements from `iter` and return them as an array.
+///
+/// Return `None` if the iterator ran out before `N` elements; the elements
+/// that were pulled are dropped in that case. No more elements than needed
+/// are pulled from the iterator.
+///
+/// ```
+/// use odds::slice::fill_array;
+///
+/// let mut iter = "a b c d e".split(' ');
+/// assert_eq!(fill_array::<_, 2>(&mut iter), Some(["a", "b"]));
+/// assert_eq!(fill_array::<_, 2>(&mut iter), Some(["c", "d"]));
+/// assert_eq!(fill_array::<_, 2>(&mut iter), None);
+/// ```
+pub fn fill_array<I, const N: usize>(iter: I) -> Option<[I::Item; N]>
+    where I: IntoIterator,
+{
+    let mut iter = iter.into_iter();
+    unsafe {
+        let mut array: [MaybeUninit<I::Item>; N] = MaybeUninit::uninit().assume_init();
+        {
+            let mut guard = PartialArrayGuard { data: &mut array, len: 0 };
+            while guard.len < N {
+                match iter.next() {
+                    Some(elt) => {
+                        guard.data[guard.len] = MaybeUninit::new(elt);
+                        guard.len += 1;
+                    }
+                    None => return None,
+                }
+            }
+            mem::forget(guard);
+        }
+        Some(ptr::read(&array as *const _ as *const [I::Item; N]))
+    }
+}
+
+#[test]
+fn test_fill_array() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut iter = (0..5).map(|_| rc.clone());
+    let array = fill_array::<_, 3>(&mut iter);
+    assert!(array.is_some());
+    assert_eq!(Rc::strong_count(&rc), 4);
+    assert!(fill_array::<_, 3>(&mut iter).is_none());
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(array);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    assert_eq!(fill_array::<_, 0>(None::<i32>), Some([]));
+}
+
+/// Return the index of the first occurrence of `byte` in `data`, or `None`.
+///
+/// This checks eight bytes at a time, which is much faster than
+/// `SliceFind::find` for long byte slices.
+///
+/// ```
+/// use odds::slice::{find_byte, rfind_byte};
+///
+/// let data = b"key=value=x";
+/// assert_eq!(find_byte(data, b'='), Some(3));
+/// assert_eq!(rfind_byte(data, b'='), Some(9));
+/// assert_eq!(find_byte(data, b'?'), None);
+/// ```
+pub fn find_byte(data: &[u8], byte: u8) -> Option<usize> {
+    let pattern = BYTES_LO * byte as u64;
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        unsafe {
+            let x = u64::from_le(load_u64(data, i)) ^ pattern;
+            let zeros = x.wrapping_sub(BYTES_LO) & !x & BYTES_HI;
+            if zeros != 0 {
+                // the lowest flagged byte is always a real match
+                return Some(i + zeros.trailing_zeros() as usize / 8);
+            }
+        }
+        i += 8;
+    }
+    data[i..].iter().position(|&b| b == byte).map(|j| i + j)
+}
+
+/// Return the index of the last occurrence of `byte` in `data`, or `None`.
+///
+/// This checks eight bytes at a time, which is much faster than
+/// `SliceFind::rfind` for long byte slices.
+pub fn rfind_byte(data: &[u8], byte: u8) -> Option<usize> {
+    let pattern = BYTES_LO * byte as u64;
+    let mut end = data.len();
+    while end >= 8 {
+        unsafe {
+            let x = u64::from_le(load_u64(data, end - 8)) ^ pattern;
+            // this test has no false positives, unlike the one in find_byte
+            let zeros = !(((x & !BYTES_HI) + !BYTES_HI) | x | !BYTES_HI);
+            if zeros != 0 {
+                return Some(end - 8 + (63 - zeros.leading_zeros() as usize) / 8);
+            }
+        }
+        end -= 8;
+    }
+    data[..end].iter().rposition(|&b| b == byte)
+}
+
+/// Return the index of the first occurrence of `byte` in `data`, or `None`.
+///
+/// Like `find_byte`, for `i8`.
+pub fn find_byte_i8(data: &[i8], byte: i8) -> Option<usize> {
+    find_byte(i8_as_bytes(data), byte as u8)
+}
+
+/// Return the index of the last occurrence of `byte` in `data`, or `None`.
+///
+/// Like `rfind_byte`, for `i8`.
+pub fn rfind_byte_i8(data: &[i8], byte: i8) -> Option<usize> {
+    rfind_byte(i8_as_bytes(data), byte as u8)
+}
+
+/// Return the index of the first byte in `data` that is `a` or `b`, or
+/// `None`.
+///
+/// Like `find_byte`, this checks eight bytes at a time.
+fn find_byte2(data: &[u8], a: u8, b: u8) -> Option<usize> {
+    let pattern_a = BYTES_LO * a as u64;
+    let pattern_b = BYTES_LO * b as u64;
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        unsafe {
+            let word = u64::from_le(load_u64(data, i));
+            let xa = word ^ pattern_a;
+            let xb = word ^ pattern_b;
+            // the lowest flagged byte of each is a real match, so the
+            // lowest of both is the first match
+            let zeros = (xa.wrapping_sub(BYTES_LO) & !xa | xb.wrapping_sub(BYTES_LO) & !xb)
+                        & BYTES_HI;
+            if zeros != 0 {
+                return Some(i + zeros.trailing_zeros() as usize / 8);
+            }
+        }
+        i += 8;
+    }
+    data[i..].iter().position(|&x| x == a || x == b).map(|j| i + j)
+}
+
+fn i8_as_bytes(data: &[i8]) -> &[u8] {
+    unsafe {
+        from_raw_parts(data.as_ptr() as *const u8, data.len())
+    }
+}
+
+#[test]
+fn test_find_byte() {
+    let mut data = [0u8; 40];
+    for start in 0..data.len() {
+        for i in start..data.len() {
+            data[i] = 0x80;
+            let s = &data[start..];
+            assert_eq!(find_byte(s, 0x80), Some(i - start));
+            assert_eq!(rfind_byte(s, 0x80), Some(i - start));
+            assert_eq!(find_byte2(s, 1, 0x80), Some(i - start));
+            assert_eq!(find_byte(s, 1), None);
+            assert_eq!(rfind_byte(s, 1), None);
+            data[i] = 0;
+        }
+    }
+    let data = [1u8, 2, 1, 1, 0x81, 1, 0, 0, 1, 2, 3, 1, 1];
+    assert_eq!(rfind_byte(&data, 2), Some(9));
+    assert_eq!(rfind_byte(&data, 1), Some(12));
+    assert_eq!(rfind_byte(&data[..12], 0), Some(7));
+    assert_eq!(find_byte(&data, 0), Some(6));
+    let signed = [1i8, -1, 0, -1];
+    assert_eq!(find_byte_i8(&signed, -1), Some(1));
+    assert_eq!(rfind_byte_i8(&signed, -1), Some(3));
+    assert_eq!(find_byte2(&data, 0x81, 0), Some(4));
+    assert_eq!(find_byte2(&data, 3, 0), Some(6));
+    assert_eq!(find_byte2(&data[7..], 3, 2), Some(2));
+    assert_eq!(find_byte2(&data, 4, 5), None);
+    assert_eq!(data.find(&2), Some(1));
+    assert_eq!(data.rfind(&2), Some(9));
+    assert_eq!(signed.rfind(&-1), Some(3));
+}
+
+/// Return a word with the high bit set in each byte of `x` that is an ASCII
+/// letter in `lo..=hi`, and all other bits clear.
+fn ascii_range_mask(x: u64, lo: u8, hi: u8) -> u64 {
+    // the low seven bits of a byte plus these don't carry into the next byte
+    let low = x & !BYTES_HI;
+    let ge_lo = low + BYTES_LO * (0x80 - lo) as u64;
+    let gt_hi = low + BYTES_LO * (0x7F - hi) as u64;
+    (ge_lo ^ gt_hi) & !x & BYTES_HI
+}
+
+/// Lowercase every ASCII letter of `x`, a word of bytes.
+fn ascii_lowercase_word(x: u64) -> u64 {
+    // 0x80 >> 2 is the case bit, 0x20
+    x | ascii_range_mask(x, b'A', b'Z') >> 2
+}
+
+/// Convert the ASCII letters in `data` to lowercase, in place.
+///
+/// Like `<[u8]>::make_ascii_lowercase`, but converts eight bytes at a time.
+/// Non-ASCII bytes are left unchanged.
+///
+/// ```
+/// use odds::slice::make_ascii_lowercase_fast;
+///
+/// let mut header = *b"Content-Type: Text/HTML";
+/// make_ascii_lowercase_fast(&mut header);
+/// assert_eq!(&header, b"content-type: text/html");
+/// ```
+pub fn make_ascii_lowercase_fast(data: &mut [u8]) {
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        unsafe {
+            let x = load_u64(data, i);
+            store_u64(data, i, ascii_lowercase_word(x));
+        }
+        i += 8;
+    }
+    data[i..].make_ascii_lowercase();
+}
+
+/// Convert the ASCII letters in `data` to uppercase, in place.
+///
+/// Like `<[u8]>::make_ascii_uppercase`, but converts eight bytes at a time.
+/// Non-ASCII bytes are left unchanged.
+pub fn make_ascii_uppercase_fast(data: &mut [u8]) {
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        unsafe {
+            let x = load_u64(data, i);
+            store_u64(data, i, x & !(ascii_range_mask(x, b'a', b'z') >> 2));
+        }
+        i += 8;
+    }
+    data[i..].make_ascii_uppercase();
+}
+
+/// Return `true` if `a` and `b` are equal, ignoring the case of ASCII
+/// letters.
+///
+/// Like `<[u8]>::eq_ignore_ascii_case`, but compares eight bytes at a
+/// time. For strings, compare their `.as_bytes()`.
+///
+/// ```
+/// use odds::slice::eq_ignore_ascii_case_fast;
+///
+/// assert!(eq_ignore_ascii_case_fast(b"Transfer-Encoding", b"transfer-encoding"));
+/// assert!(!eq_ignore_ascii_case_fast(b"Transfer-Encoding", b"transfer_encoding"));
+/// ```
+pub fn eq_ignore_ascii_case_fast(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i + 8 <= a.len() {
+        unsafe {
+            let x = ascii_lowercase_word(load_u64(a, i));
+            let y = ascii_lowercase_word(load_u64(b, i));
+            if x != y {
+                return false;
+            }
+        }
+        i += 8;
+    }
+    a[i..].eq_ignore_ascii_case(&b[i..])
+}
+
+#[test]
+fn test_ascii_case_fast() {
+    // every byte value, at every offset into the words
+    let all = (0..256).map(|b| b as u8).collect::<Vec<_>>();
+    for start in 0..8 {
+        let mut lower = all[start..].to_vec();
+        let mut upper = lower.clone();
+        make_ascii_lowercase_fast(&mut lower);
+        make_ascii_uppercase_fast(&mut upper);
+        assert_eq!(lower, all[start..].to_ascii_lowercase());
+        assert_eq!(upper, all[start..].to_ascii_uppercase());
+        assert!(eq_ignore_ascii_case_fast(&lower, &upper));
+        assert!(eq_ignore_ascii_case_fast(&upper, &all[start..]));
+        for i in 0..lower.len() {
+            let mut other = lower.clone();
+            other[i] ^= 0x40;
+            assert_eq!(eq_ignore_ascii_case_fast(&upper, &other),
+                       upper.eq_ignore_ascii_case(&other));
+        }
+    }
+    assert!(!eq_ignore_ascii_case_fast(b"ab", b"abc"));
+    // '@' and '`' differ from letters only in the case bit
+    assert!(!eq_ignore_ascii_case_fast(b"@[\\]^_@[\\]^_", b"`{|}~\x7f`{|}~\x7f"));
+}
+
+/// Element-finding methods for slices
+///
+/// With the `unstable` feature, searching `[u8]` for a `u8` (or `[i8]` for
+/// an `i8`) uses `find_byte` and `rfind_byte`. Without it, call those
+/// directly for long byte slices.
+pub trait SliceFind {
+    type Item;
+    /// Linear search for the first occurrence  `elt` in the slice.
+    ///
+    /// Return its index if it is found, or None.
+    fn find<U: ?Sized>(&self, elt: &U) -> Option<usize>
+        where Self::Item: PartialEq<U>;
+
+    /// Linear search for the last occurrence  `elt` in the slice.
+    ///
+    /// Return its index if it is found, or None.
+    fn rfind<U: ?Sized>(&self, elt: &U) -> Option<usize>
+        where Self::Item: PartialEq<U>;
+}
+
+macro_rules! foreach {
+    ($i:pat in $($e:expr),* => $b:expr) => {{
+        $(
+            let $i = $e;
+            $b;
+        )*
+    }}
+}
+
+impl<T> SliceFind for [T] { 
+    type Item = T;
+    fn find<U: ?Sized>(&self, elt: &U) -> Option<usize>
+        where Self::Item: PartialEq<U>
+    {
+        FindElem::find_elem(self, elt)
+    }
+
+    fn rfind<U: ?Sized>(&self, elt: &U) -> Option<usize>
+        where Self::Item: PartialEq<U>
+    {
+        FindElem::rfind_elem(self, elt)
+    }
+}
+
+/// The search behind `SliceFind`, which the `unstable` feature specializes
+/// for bytes.
+trait FindElem<U: ?Sized> : Sized {
+    fn find_elem(data: &[Self], elt: &U) -> Option<usize>;
+    fn rfind_elem(data: &[Self], elt: &U) -> Option<usize>;
+}
+
+macro_rules! impl_find_elem {
+    ($($default:ident)*) => {
+        impl<T, U: ?Sized> FindElem<U> for T
+            where T: PartialEq<U>
+        {
+            $($default)* fn find_elem(data: &[T], elt: &U) -> Option<usize> {
+                SliceIter::from(data).position(move |x| *x == *elt)
+            }
+
+            $($default)* fn rfind_elem(data: &[T], elt: &U) -> Option<usize> {
+                SliceIter::from(data).rposition(move |x| *x == *elt)
+            }
+        }
+    }
+}
+
+#[cfg(feature="unstable")]
+impl_find_elem!(default);
+#[cfg(not(feature="unstable"))]
+impl_find_elem!();
+
+#[cfg(feature="unstable")]
+impl FindElem<u8> for u8 {
+    fn find_elem(data: &[u8], elt: &u8) -> Option<usize> {
+        find_byte(data, *elt)
+    }
+
+    fn rfind_elem(data: &[u8], elt: &u8) -> Option<usize> {
+        rfind_byte(data, *elt)
+    }
+}
+
+#[cfg(feature="unstable")]
+impl FindElem<i8> for i8 {
+    fn find_elem(data: &[i8], elt: &i8) -> Option<usize> {
+        find_byte_i8(data, *elt)
+    }
+
+    fn rfind_elem(data: &[i8], elt: &i8) -> Option<usize> {
+        rfind_byte_i8(data, *elt)
+    }
+}
+
+/// Element-finding methods for slices
+pub trait SliceFindSplit {
+    type Item;
+    /// Linear search for the first occurrence  `elt` in the slice.
+    ///
+    /// Return the part before and the part including and after the element.
+    /// If the element is not found, the second half is empty.
+    fn find_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
+        where Self::Item: PartialEq<U>;
+
+    /// Linear search for the last occurrence  `elt` in the slice.
+    ///
+    /// Return the part before and the part including and after the element.
+    /// If the element is not found, the first half is empty.
+    fn rfind_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
+        where Self::Item: PartialEq<U>;
+
+    /// Linear search for the first occurrence  `elt` in the slice.
+    ///
+    /// Return the part before and the part including and after the element.
+    /// If the element is not found, the second half is empty.
+    fn find_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
+        where Self::Item: PartialEq<U>;
+
+    /// Linear search for the last occurrence  `elt` in the slice.
+    ///
+    /// Return the part before and the part including and after the element.
+    /// If the element is not found, the first half is empty.
+    fn rfind_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
+        where Self::Item: PartialEq<U>;
+}
+
+
+/// Unchecked version of `xs.split_at(i)`.
+unsafe fn split_at_unchecked<T>(xs: &[T], i: usize) -> (&[T], &[T]) {
+    (slice_unchecked(xs, 0, i),
+     slice_unchecked(xs, i, xs.len()))
+}
+
+impl<T> SliceFindSplit for [T] { 
+    type Item = T;
+    fn find_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
+        where Self::Item: PartialEq<U>
+    {
+        let i = self.find(elt).unwrap_or(self.len());
+        unsafe {
+            split_at_unchecked(self, i)
+        }
+    }
+
+    fn find_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
+        where Self::Item: PartialEq<U>
+    {
+        let i = self.find(elt).unwrap_or(self.len());
+        self.split_at_mut(i)
+    }
+
+    fn rfind_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
+        where Self::Item: PartialEq<U>
+    {
+        let i = self.rfind(elt).unwrap_or(0);
+        unsafe {
+            split_at_unchecked(self, i)
+        }
+    }
+
+    fn rfind_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
+        where Self::Item: PartialEq<U>
+    {
+        let i = self.rfind(elt).unwrap_or(0);
+        self.split_at_mut(i)
+    }
+}
+
+
+/// Extra methods for slices
+pub trait SliceExt {
+    type Item;
+    /// Return mutable references to the elements at each of the `indices`,
+    /// or `None` if any index is out of bounds or two of them are equal.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [1, 2, 3, 4];
+    /// if let Some([a, b]) = data.get_many_mut([3, 0]) {
+    ///     *a += 10;
+    ///     *b += 20;
+    /// }
+    /// assert_eq!(data, [21, 2, 3, 14]);
+    /// assert!(data.get_many_mut([1, 1]).is_none());
+    /// assert!(data.get_many_mut([1, 4]).is_none());
+    /// ```
+    fn get_many_mut<const N: usize>(&mut self, indices: [usize; N])
+        -> Option<[&mut Self::Item; N]>;
+
+    /// Rotate `steps` towards lower indices, modulo the length.
+    ///
+    /// See [`rotate_left`](fn.rotate_left.html); unlike the inherent slice
+    /// method `rotate_left`, this does not panic for any `steps`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [1, 2, 3];
+    /// data.rotate_left_mod(4);
+    /// assert_eq!(data, [2, 3, 1]);
+    /// ```
+    fn rotate_left_mod(&mut self, steps: usize);
+
+    /// Rotate `steps` towards higher indices, modulo the length.
+    ///
+    /// See [`rotate_right`](fn.rotate_right.html).
+    fn rotate_right_mod(&mut self, steps: usize);
+
+    /// Binary search a sorted slice for the first index where `f` does not
+    /// return `Less`; this is where an element equal to the target could be
+    /// inserted, before any equal elements.
+    ///
+    /// `f` compares an element to the target, like in `binary_search_by`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let data = [1, 2, 2, 2, 5];
+    /// assert_eq!(data.lower_bound_by(|x| x.cmp(&2)), 1);
+    /// assert_eq!(data.upper_bound_by(|x| x.cmp(&2)), 4);
+    /// assert_eq!(data.equal_range_by(|x| x.cmp(&2)), 1..4);
+    /// assert_eq!(data.equal_range_by(|x| x.cmp(&3)), 4..4);
+    /// ```
+    fn lower_bound_by<F>(&self, f: F) -> usize
+        where F: FnMut(&Self::Item) -> Ordering;
+
+    /// Binary search a sorted slice for the first index where `f` returns
+    /// `Greater`; this is where an element equal to the target could be
+    /// inserted, after any equal elements.
+    fn upper_bound_by<F>(&self, f: F) -> usize
+        where F: FnMut(&Self::Item) -> Ordering;
+
+    /// Binary search a sorted slice for the range of elements where `f`
+    /// returns `Equal`. If there are none, the range is empty, and starts
+    /// where such an element could be inserted.
+    fn equal_range_by<F>(&self, f: F) -> Range<usize>
+        where F: FnMut(&Self::Item) -> Ordering;
+
+    /// Set every element to `value`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [0u32; 4];
+    /// data[1..].fill_copy(7);
+    /// assert_eq!(data, [0, 7, 7, 7]);
+    /// ```
+    fn fill_copy(&mut self, value: Self::Item)
+        where Self::Item: Copy;
+
+    /// Set every element to `value`, with a single `memset`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut pixels = [7u8; 16];
+    /// pixels.fill_byte(0);
+    /// assert_eq!(pixels, [0; 16]);
+    /// ```
+    fn fill_byte(&mut self, value: Self::Item)
+        where Self::Item: ByteValue;
+
+    /// Set every element to the return value of `f`, called once for each
+    /// element, front to back.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut n = 0;
+    /// let mut data = [0; 4];
+    /// data.fill_with_fn(|| { n += 2; n });
+    /// assert_eq!(data, [2, 4, 6, 8]);
+    /// ```
+    fn fill_with_fn<F>(&mut self, f: F)
+        where F: FnMut() -> Self::Item;
+
+    /// Copy the elements in `src` to the position starting at `dest`, in
+    /// the same slice. The ranges may overlap.
+    ///
+    /// **Panics** if either range is out of bounds.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// // shift the unconsumed part of a buffer to the front
+    /// let mut buf = *b"consumed rest";
+    /// buf.copy_within_range(9.., 0);
+    /// assert_eq!(&buf[..4], b"rest");
+    /// ```
+    fn copy_within_range<R>(&mut self, src: R, dest: usize)
+        where R: IndexRange,
+              Self::Item: Copy;
+
+    /// Swap the elements at indices `a` and `b`, without bounds checks in
+    /// release mode.
+    ///
+    /// The indices may be equal.
+    ///
+    /// **Unsafe** because `a` and `b` must be in bounds; this is only checked
+    /// with `debug_assert!`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [1, 2, 3];
+    /// unsafe {
+    ///     SliceExt::swap_unchecked(&mut data[..], 0, 2);
+    /// }
+    /// assert_eq!(data, [3, 2, 1]);
+    /// ```
+    unsafe fn swap_unchecked(&mut self, a: usize, b: usize);
+
+    /// Return the subslice in `range`, without bounds checks in release
+    /// mode.
+    ///
+    /// **Unsafe** because the range must be in bounds and not decreasing;
+    /// this is only checked with `debug_assert!`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let data = [1, 2, 3, 4];
+    /// unsafe {
+    ///     assert_eq!(data.get_unchecked_range(1..3), &[2, 3]);
+    ///     assert_eq!(data.get_unchecked_range(2..), &[3, 4]);
+    /// }
+    /// ```
+    unsafe fn get_unchecked_range<R>(&self, range: R) -> &[Self::Item]
+        where R: IndexRange;
+
+    /// Return the subslice in `range` mutably, without bounds checks in
+    /// release mode.
+    ///
+    /// **Unsafe** for the same reasons as `.get_unchecked_range()`.
+    unsafe fn get_unchecked_range_mut<R>(&mut self, range: R) -> &mut [Self::Item]
+        where R: IndexRange;
+
+    /// Shuffle the elements in place, using random numbers from `rng`.
+    ///
+    /// Each permutation is equally likely, as far as `rng` is uniform
+    /// (Fisher–Yates shuffle).
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    /// use odds::random::XorShift;
+    ///
+    /// let mut rng = XorShift::new(42);
+    /// let mut deck = [1, 2, 3, 4, 5, 6, 7, 8];
+    /// deck.shuffle(&mut rng);
+    /// deck.sort();
+    /// assert_eq!(deck, [1, 2, 3, 4, 5, 6, 7, 8]);
+    /// ```
+    fn shuffle<R>(&mut self, rng: &mut R)
+        where R: RandomSource + ?Sized;
+
+    /// Reorder the elements so that all elements where `pred` returns
+    /// `true` come before all elements where it returns `false`, and return
+    /// the number of `true` elements.
+    ///
+    /// The relative order of the elements is not preserved. `pred` is
+    /// called exactly once for each element.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [7, 2, 9, 4, 1, 8];
+    /// let mid = data.partition_in_place(|&x| x < 5);
+    /// assert_eq!(mid, 3);
+    /// assert!(data[..mid].iter().all(|&x| x < 5));
+    /// assert!(data[mid..].iter().all(|&x| x >= 5));
+    /// ```
+    fn partition_in_place<F>(&mut self, pred: F) -> usize
+        where F: FnMut(&Self::Item) -> bool;
+
+    /// Collapse each run of consecutive equal elements into its first
+    /// element, and call `f` with that element and the length of the run.
+    ///
+    /// The first elements of the runs are moved to the front of the slice,
+    /// in order; return their number. The rest of the slice is left holding
+    /// the duplicates in unspecified order.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut ids = vec![3, 3, 3, 7, 8, 8, 3];
+    /// let mut counts = Vec::new();
+    /// let n = ids.dedup_with_count(|&id, count| counts.push((id, count)));
+    /// ids.truncate(n);
+    /// assert_eq!(ids, [3, 7, 8, 3]);
+    /// assert_eq!(counts, [(3, 3), (7, 1), (8, 2), (3, 1)]);
+    /// ```
+    fn dedup_with_count<F>(&mut self, f: F) -> usize
+        where Self::Item: PartialEq,
+              F: FnMut(&Self::Item, usize);
+
+    /// Return the index of the minimum element, or `None` if the slice is
+    /// empty. If several elements are equally minimum, the first is
+    /// returned.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let scores = [4, 1, 7, 1, 7];
+    /// assert_eq!(scores.position_min(), Some(1));
+    /// assert_eq!(scores.position_max(), Some(4));
+    /// ```
+    fn position_min(&self) -> Option<usize>
+        where Self::Item: Ord;
+
+    /// Return the index of the maximum element, or `None` if the slice is
+    /// empty. If several elements are equally maximum, the last is
+    /// returned.
+    fn position_max(&self) -> Option<usize>
+        where Self::Item: Ord;
+
+    /// Return the index of the element with the minimum key, or `None` if
+    /// the slice is empty. Ties are resolved like in `.position_min()`.
+    ///
+    /// The key function is called once for each element.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let points: [(i32, i32); 3] = [(1, -3), (0, 2), (-1, 0)];
+    /// assert_eq!(points.position_min_by_key(|p| p.0.abs() + p.1.abs()), Some(2));
+    /// assert_eq!(points.position_max_by_key(|p| p.0.abs() + p.1.abs()), Some(0));
+    /// ```
+    fn position_min_by_key<K, F>(&self, key: F) -> Option<usize>
+        where K: Ord,
+              F: FnMut(&Self::Item) -> K;
+
+    /// Return the index of the element with the maximum key, or `None` if
+    /// the slice is empty. Ties are resolved like in `.position_max()`.
+    ///
+    /// The key function is called once for each element.
+    fn position_max_by_key<K, F>(&self, key: F) -> Option<usize>
+        where K: Ord,
+              F: FnMut(&Self::Item) -> K;
+
+    /// Split the slice into arrays of `N` elements, and the remainder of
+    /// less than `N` elements.
+    ///
+    /// **Panics** if `N` is zero.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let data = [1, 2, 3, 4, 5, 6, 7];
+    /// let (chunks, rest): (&[[i32; 3]], _) = data.as_array_chunks();
+    /// assert_eq!(chunks, &[[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(rest, &[7]);
+    /// ```
+    fn as_array_chunks<const N: usize>(&self) -> (&[[Self::Item; N]], &[Self::Item]);
+
+    /// Split the slice into mutable arrays of `N` elements, and the
+    /// remainder of less than `N` elements.
+    ///
+    /// **Panics** if `N` is zero.
+    fn as_array_chunks_mut<const N: usize>(&mut self)
+        -> (&mut [[Self::Item; N]], &mut [Self::Item]);
+
+    /// Return an iterator of `n` contiguous subslices, whose lengths differ
+    /// by at most one. If the slice has less than `n` elements, some of the
+    /// subslices are empty.
+    ///
+    /// **Panics** if `n` is zero.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let work = [0; 11];
+    /// let sizes = work.split_n(4).map(|s| s.len()).collect::<Vec<_>>();
+    /// assert_eq!(sizes, [3, 3, 3, 2]);
+    /// ```
+    fn split_n<'a>(&'a self, n: usize) -> BalancedChunks<'a, Self::Item>;
+
+    /// Return an iterator of `n` contiguous mutable subslices, whose lengths
+    /// differ by at most one.
+    ///
+    /// **Panics** if `n` is zero.
+    fn split_n_mut<'a>(&'a mut self, n: usize) -> BalancedChunksMut<'a, Self::Item>;
+
+    /// Return `true` if the elements are in nondecreasing order.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// assert!([1, 2, 2, 9].is_ordered());
+    /// assert!(![1, 3, 2].is_ordered());
+    /// ```
+    fn is_ordered(&self) -> bool
+        where Self::Item: PartialOrd;
+
+    /// Return `true` if `in_order(a, b)` returns `true` for each pair of
+    /// adjacent elements `a` and `b`.
+    ///
+    /// Stops at the first pair that is out of order.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let data = [5, 3, 3, 1];
+    /// assert!(data.is_ordered_by(|a, b| a >= b));
+    /// assert!(!data.is_ordered_by(|a, b| a > b));
+    /// ```
+    fn is_ordered_by<F>(&self, in_order: F) -> bool
+        where F: FnMut(&Self::Item, &Self::Item) -> bool;
+
+    /// Return `true` if the keys of the elements are in nondecreasing order.
+    ///
+    /// The key function is called at most once for each element.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let words = ["a", "to", "the", "then"];
+    /// assert!(words.is_ordered_by_key(|w| w.len()));
+    /// ```
+    fn is_ordered_by_key<K, F>(&self, key: F) -> bool
+        where K: PartialOrd,
+              F: FnMut(&Self::Item) -> K;
+
+    /// Reorder the slice so that the element at `index` is the one that
+    /// would be there if the slice was sorted, with all elements before it
+    /// less than or equal, and all elements after it greater than or equal.
+    ///
+    /// Return the part before `index`, the element at `index`, and the part
+    /// after `index`. This runs in linear time in the worst case: when
+    /// quickselect does badly, it falls back to median-of-medians pivots.
+    ///
+    /// **Panics** if `index` is out of bounds.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut samples = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+    /// let (lower, median, upper) = samples.select_nth(4);
+    /// assert_eq!(*median, 5);
+    /// assert!(lower.iter().all(|&x| x <= 5));
+    /// assert!(upper.iter().all(|&x| x >= 5));
+    /// ```
+    fn select_nth(&mut self, index: usize)
+        -> (&mut [Self::Item], &mut Self::Item, &mut [Self::Item])
+        where Self::Item: Ord;
+
+    /// Like `.select_nth()`, but the order is given by
+    /// `less_than`.
+    ///
+    /// **Panics** if `index` is out of bounds.
+    fn select_nth_by<F>(&mut self, index: usize, less_than: F)
+        -> (&mut [Self::Item], &mut Self::Item, &mut [Self::Item])
+        where F: FnMut(&Self::Item, &Self::Item) -> bool;
+
+    /// Return the permutation that sorts the slice: the indices of the
+    /// elements, in sorted order of the elements. The slice is not modified.
+    ///
+    /// The sort is stable. Use `.apply_permutation()` to reorder this or
+    /// other slices of the same length by the permutation.
+    ///
+    /// Requires `feature="std"`
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut ages = [41, 23, 35];
+    /// let mut names = ["ann", "bo", "cy"];
+    /// let perm = ages.sort_permutation();
+    /// assert_eq!(perm, [1, 2, 0]);
+    /// ages.apply_permutation(&perm);
+    /// names.apply_permutation(&perm);
+    /// assert_eq!(ages, [23, 35, 41]);
+    /// assert_eq!(names, ["bo", "cy", "ann"]);
+    /// ```
+    #[cfg(feature = "std")]
+    fn sort_permutation(&self) -> Vec<usize>
+        where Self::Item: Ord;
+
+    /// Reorder the slice so that the element at index `i` is the element
+    /// that was at index `perm[i]`.
+    ///
+    /// **Panics** if `perm` is not a permutation of the indices of the
+    /// slice.
+    ///
+    /// Requires `feature="std"`
+    #[cfg(feature = "std")]
+    fn apply_permutation(&mut self, perm: &[usize]);
+
+    /// Reorder the elements so that all elements where `pred` returns
+    /// `true` come before all elements where it returns `false`, keeping
+    /// the relative order within both groups. Return the number of `true`
+    /// elements.
+    ///
+    /// `pred` is called exactly once for each element. This does not
+    /// allocate, and uses `O(n log n)` moves.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut items = ["b1", "a1", "b2", "a2", "b3"];
+    /// let n = items.stable_partition(|s| s.starts_with('a'));
+    /// assert_eq!(n, 2);
+    /// assert_eq!(items, ["a1", "a2", "b1", "b2", "b3"]);
+    /// ```
+    fn stable_partition<F>(&mut self, pred: F) -> usize
+        where F: FnMut(&Self::Item) -> bool;
+
+    /// Like `.stable_partition()`, but using `buf` as scratch space.
+    ///
+    /// If `buf` is as long as the slice, this uses a linear number of moves;
+    /// shorter buffers still reduce the work.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let mut data = [1, 2, 3, 4, 5, 6, 7];
+    /// let mut buf = [0; 4];
+    /// let n = data.stable_partition_buffered(|&x| x % 3 == 0, &mut buf);
+    /// assert_eq!(&data[..n], &[3, 6]);
+    /// assert_eq!(&data[n..], &[1, 2, 4, 5, 7]);
+    /// ```
+    fn stable_partition_buffered<F>(&mut self, pred: F, buf: &mut [Self::Item]) -> usize
+        where Self::Item: Copy,
+              F: FnMut(&Self::Item) -> bool;
+
+    /// Search a sorted slice for the first index where the element is not
+    /// less than `key`, like `.lower_bound_by()`.
+    ///
+    /// Probes the indices `0, 1, 3, 7, 15, ...` until it passes the key,
+    /// then binary searches the last interval; this takes `O(log i)`
+    /// comparisons for a result `i`, which is faster than a binary search
+    /// when the result is near the front.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let run = [1, 2, 2, 2, 5, 8, 9, 12, 13, 20];
+    /// assert_eq!(run.gallop_left(&2), 1);
+    /// assert_eq!(run.gallop_right(&2), 4);
+    /// assert_eq!(run.gallop_left(&21), 10);
+    /// ```
+    fn gallop_left(&self, key: &Self::Item) -> usize
+        where Self::Item: Ord;
+
+    /// Search a sorted slice for the first index where the element is
+    /// greater than `key`, like `.upper_bound_by()`.
+    ///
+    /// Probes exponentially from the front, like `.gallop_left()`.
+    fn gallop_right(&self, key: &Self::Item) -> usize
+        where Self::Item: Ord;
+
+    /// Call `f` with each window of `size` adjacent elements, front to back,
+    /// as a mutable slice.
+    ///
+    /// See [`for_each_window_mut`](fn.for_each_window_mut.html).
+    ///
+    /// **Panics** if `size` is zero.
+    fn for_each_window_mut<F>(&mut self, size: usize, f: F)
+        where F: FnMut(&mut [Self::Item]);
+
+    /// Call `f` with each pair of adjacent elements, front to back, with
+    /// both elements mutable.
+    ///
+    /// See [`for_each_pair_mut`](fn.for_each_pair_mut.html).
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// // relax distances between neighbours towards at most 2
+    /// let mut pos = [0, 5, 6, 12];
+    /// pos.for_each_pair_mut(|a, b| if *b - *a > 2 { *b = *a + 2 });
+    /// assert_eq!(pos, [0, 2, 4, 6]);
+    /// ```
+    fn for_each_pair_mut<F>(&mut self, f: F)
+        where F: FnMut(&mut Self::Item, &mut Self::Item);
+
+    /// Return the slice without the leading elements where `pred` returns
+    /// `true`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let frame = [0u8, 0, 7, 1, 0, 0];
+    /// assert_eq!(frame.trim_start_matches(|&b| b == 0), &[7, 1, 0, 0]);
+    /// assert_eq!(frame.trim_end_matches(|&b| b == 0), &[0, 0, 7, 1]);
+    /// assert_eq!(frame.trim_matches(|&b| b == 0), &[7, 1]);
+    /// ```
+    fn trim_start_matches<F>(&self, pred: F) -> &[Self::Item]
+        where F: FnMut(&Self::Item) -> bool;
+
+    /// Return the slice without the trailing elements where `pred` returns
+    /// `true`.
+    fn trim_end_matches<F>(&self, pred: F) -> &[Self::Item]
+        where F: FnMut(&Self::Item) -> bool;
+
+    /// Return the slice without the leading and trailing elements where
+    /// `pred` returns `true`.
+    fn trim_matches<F>(&self, pred: F) -> &[Self::Item]
+        where F: FnMut(&Self::Item) -> bool;
+
+    /// Return the rest of the slice if it starts with `prefix`, otherwise
+    /// `None`.
+    ///
+    /// ```
+    /// use odds::slice::SliceExt;
+    ///
+    /// let msg = b"GET /index.html";
+    /// assert_eq!(msg.without_prefix(b"GET "), Some(&b"/index.html"[..]));
+    /// assert_eq!(msg.without_prefix(b"PUT "), None);
+    /// assert_eq!(msg.without_suffix(b".html"), Some(&b"GET /index"[..]));
+    /// ```
+    fn without_prefix(&self, prefix: &[Self::Item]) -> Option<&[Self::Item]>
+        where Self::Item: PartialEq;
+
+    /// Return the start of the slice if it ends with `suffix`, otherwise
+    /// `None`.
+    fn without_suffix(&self, suffix: &[Self::Item]) -> Option<&[Self::Item]>
+        where Self::Item: PartialEq;
+}
+
+/// Return the first index where `before(elt)` is `false`, if the slice is
+/// partitioned by `before`. Probe exponentially from the front, then binary
+/// search.
+fn gallop<T, F>(data: &[T], mut before: F) -> usize
+    where F: FnMut(&T) -> bool
+{
+    // invariant: before(data[i]) is true for all i < lo
+    let mut lo = 0;
+    let mut probe = 0;
+    while probe < data.len() && before(&data[probe]) {
+        lo = probe + 1;
+        probe = 2 * probe + 1;
+    }
+    let hi = min(probe, data.len());
+    lo + data[lo..hi].lower_bound_by(|x| {
+        if before(x) { Ordering::Less } else { Ordering::Greater }
+    })
+}
+
+/// Stable partition by divide and conquer: partition both halves, then
+/// rotate the false part of the front half past the true part of the back.
+fn stable_partition_rec<T, F>(data: &mut [T], pred: &mut F) -> usize
+    where F: FnMut(&T) -> bool
+{
+    let len = data.len();
+    if len <= 1 {
+        return (len == 1 && pred(&data[0])) as usize;
+    }
+    let mid = len / 2;
+    let a = stable_partition_rec(&mut data[..mid], pred);
+    let b = stable_partition_rec(&mut data[mid..], pred);
+    rotate_left(&mut data[a..mid + b], mid - a);
+    a + b
+}
+
+/// Like `stable_partition_rec`, but subslices that fit in `buf` are
+/// partitioned in one pass through it.
+fn stable_partition_buf<T, F>(data: &mut [T], pred: &mut F, buf: &mut [T]) -> usize
+    where T: Copy,
+          F: FnMut(&T) -> bool
+{
+    let len = data.len();
+    if len > buf.len() {
+        if buf.is_empty() {
+            return stable_partition_rec(data, pred);
+        }
+        let mid = len / 2;
+        let a = stable_partition_buf(&mut data[..mid], pred, buf);
+        let b = stable_partition_buf(&mut data[mid..], pred, buf);
+        rotate_left(&mut data[a..mid + b], mid - a);
+        return a + b;
+    }
+    let mut n = 0;
+    let mut rest = 0;
+    for i in 0..len {
+        if pred(&data[i]) {
+            data[n] = data[i];
+            n += 1;
+        } else {
+            buf[rest] = data[i];
+            rest += 1;
+        }
+    }
+    data[n..].copy_from_slice(&buf[..rest]);
+    n
+}
+
+/// Return the index of the median of the first, middle and last elements.
+fn median_of_three<T, F>(v: &[T], less_than: &mut F) -> usize
+    where F: FnMut(&T, &T) -> bool
+{
+    let (a, b, c) = (0, v.len() / 2, v.len() - 1);
+    let ab = less_than(&v[a], &v[b]);
+    let bc = less_than(&v[b], &v[c]);
+    if ab == bc {
+        b
+    } else if ab == less_than(&v[a], &v[c]) {
+        c
+    } else {
+        a
+    }
+}
+
+/// Return the index of an element that is guaranteed to have at least
+/// about 30% of the elements on each side of it. Reorders `v`.
+fn median_of_medians<T, F>(v: &mut [T], less_than: &mut F) -> usize
+    where F: FnMut(&T, &T) -> bool
+{
+    // move the median of each full group of five to the front
+    let groups = v.len() / 5;
+    for g in 0..groups {
+        sort_insertion_by(&mut v[5 * g..5 * g + 5], &mut *less_than);
+        v.swap(g, 5 * g + 2);
+    }
+    select_nth_impl(&mut v[..groups], groups / 2, less_than);
+    groups / 2
+}
+
+/// Reorder `v` so that `v[k]` is in its sorted position.
+fn select_nth_impl<T, F>(mut v: &mut [T], mut k: usize, less_than: &mut F)
+    where F: FnMut(&T, &T) -> bool
+{
+    // quickselect steps to try before switching to median-of-medians
+    let mut budget = 2 * (usize::max_value().count_ones() - v.len().leading_zeros());
+    loop {
+        let len = v.len();
+        if len <= 10 {
+            sort_insertion_by(v, &mut *less_than);
+            return;
+        }
+        let pivot = if budget > 0 {
+            budget -= 1;
+            median_of_three(v, less_than)
+        } else {
+            median_of_medians(v, less_than)
+        };
+        v.swap(0, pivot);
+        // three-way partition of v[1..] around the pivot in v[0]:
+        // v[1..lt] < pivot, v[lt..gt] == pivot, v[gt..] > pivot
+        let mut lt = 1;
+        let mut i = 1;
+        let mut gt = len;
+        while i < gt {
+            if less_than(&v[i], &v[0]) {
+                v.swap(lt, i);
+                lt += 1;
+                i += 1;
+            } else if less_than(&v[0], &v[i]) {
+                gt -= 1;
+                v.swap(i, gt);
+            } else {
+                i += 1;
+            }
+        }
+        v.swap(0, lt - 1);
+        let tmp = v;
+        if k < lt - 1 {
+            v = &mut tmp[..lt - 1];
+        } else if k >= gt {
+            k -= gt;
+            v = &mut tmp[gt..];
+        } else {
+            return;
+        }
+    }
+}
+
+/// Return the index of the best key in `data`, where `better(new, best)`
+/// decides if a new key replaces the best so far.
+#[inline]
+fn position_best<'a, T, K, F, B>(data: &'a [T], mut key: F, better: B) -> Option<usize>
+    where F: FnMut(&'a T) -> K,
+          B: Fn(&K, &K) -> bool
+{
+    if data.is_empty() {
+        return None;
+    }
+    let mut best_index = 0;
+    let mut best = key(&data[0]);
+    let mut i = 1;
+    macro_rules! step {
+        ($i:expr) => {{
+            let k = key(unsafe { data.get_unchecked($i) });
+            if better(&k, &best) {
+                best = k;
+                best_index = $i;
+            }
+        }}
+    }
+    while i + 4 <= data.len() {
+        step!(i);
+        step!(i + 1);
+        step!(i + 2);
+        step!(i + 3);
+        i += 4;
+    }
+    while i < data.len() {
+        step!(i);
+        i += 1;
+    }
+    Some(best_index)
+}
+
+impl<T> SliceExt for [T] {
+    type Item = T;
+    fn get_many_mut<const N: usize>(&mut self, indices: [usize; N])
+        -> Option<[&mut T; N]>
+    {
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= self.len() || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+        let ptr = self.as_mut_ptr();
+        // the indices are in bounds and distinct, so the references don't alias
+        Some(indices.map(|i| unsafe { &mut *ptr.debug_add(i) }))
+    }
+
+    fn rotate_left_mod(&mut self, steps: usize) {
+        rotate_left(self, steps)
+    }
+
+    fn rotate_right_mod(&mut self, steps: usize) {
+        rotate_right(self, steps)
+    }
+
+    fn lower_bound_by<F>(&self, mut f: F) -> usize
+        where F: FnMut(&T) -> Ordering
+    {
+        // never report Equal, so that the search runs to the boundary
+        let res = binary_search_range(self.len(), |i| match f(&self[i]) {
+            Ordering::Less => Ordering::Less,
+            _ => Ordering::Greater,
+        });
+        res.unwrap_or_else(|i| i)
+    }
+
+    fn upper_bound_by<F>(&self, mut f: F) -> usize
+        where F: FnMut(&T) -> Ordering
+    {
+        let res = binary_search_range(self.len(), |i| match f(&self[i]) {
+            Ordering::Greater => Ordering::Greater,
+            _ => Ordering::Less,
+        });
+        res.unwrap_or_else(|i| i)
+    }
+
+    fn equal_range_by<F>(&self, mut f: F) -> Range<usize>
+        where F: FnMut(&T) -> Ordering
+    {
+        let start = self.lower_bound_by(&mut f);
+        let end = start + self[start..].upper_bound_by(f);
+        start..end
+    }
+
+    fn fill_copy(&mut self, value: T)
+        where T: Copy
+    {
+        for elt in self {
+            *elt = value;
+        }
+    }
+
+    fn fill_byte(&mut self, value: T)
+        where T: ByteValue
+    {
+        unsafe {
+            ptr::write_bytes(self.as_mut_ptr() as *mut u8, value.to_byte(), self.len());
+        }
+    }
+
+    fn fill_with_fn<F>(&mut self, mut f: F)
+        where F: FnMut() -> T
+    {
+        for elt in self {
+            *elt = f();
+        }
+    }
+
+    fn copy_within_range<R>(&mut self, src: R, dest: usize)
+        where R: IndexRange,
+              T: Copy
+    {
+        let len = self.len();
+        let start = src.start().unwrap_or(0);
+        let end = src.end().unwrap_or(len);
+        assert!(start <= end && end <= len, "copy_within_range: source range out of bounds");
+        let count = end - start;
+        assert!(dest <= len - count, "copy_within_range: destination out of bounds");
+        unsafe {
+            let ptr = self.as_mut_ptr();
+            ptr::copy(ptr.debug_add(start), ptr.debug_add(dest), count);
+        }
+    }
+
+    unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        debug_assert!(a < self.len() && b < self.len());
+        let ptr = self.as_mut_ptr();
+        // ptr::swap allows a == b
+        ptr::swap(ptr.debug_add(a), ptr.debug_add(b));
+    }
+
+    unsafe fn get_unchecked_range<R>(&self, range: R) -> &[T]
+        where R: IndexRange
+    {
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(self.len());
+        slice_unchecked(self, start, end)
+    }
+
+    unsafe fn get_unchecked_range_mut<R>(&mut self, range: R) -> &mut [T]
+        where R: IndexRange
+    {
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(self.len());
+        slice_unchecked_mut(self, start, end)
+    }
+
+    fn shuffle<R>(&mut self, rng: &mut R)
+        where R: RandomSource + ?Sized
+    {
+        for i in (1..self.len()).rev() {
+            // `below` can be overridden, so its result is bounds checked
+            let j = rng.below(i + 1);
+            self.swap(i, j);
+        }
+    }
+
+    fn partition_in_place<F>(&mut self, mut pred: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        // invariant: self[..front] are true, self[back..] are false
+        let mut front = 0;
+        let mut back = self.len();
+        unsafe {
+            loop {
+                while front < back && pred(self.get_unchecked(front)) {
+                    front += 1;
+                }
+                if front == back {
+                    return front;
+                }
+                // self[front] is false; look for a true element to swap it with
+                back -= 1;
+                while front < back && !pred(self.get_unchecked(back)) {
+                    back -= 1;
+                }
+                if front == back {
+                    return front;
+                }
+                SliceExt::swap_unchecked(self, front, back);
+                front += 1;
+            }
+        }
+    }
+
+    fn dedup_with_count<F>(&mut self, mut f: F) -> usize
+        where T: PartialEq,
+              F: FnMut(&T, usize)
+    {
+        if self.is_empty() {
+            return 0;
+        }
+        // self[..write] are finished, self[write] is the head of the current run
+        let mut write = 0;
+        let mut count = 1;
+        for read in 1..self.len() {
+            if self[read] == self[write] {
+                count += 1;
+            } else {
+                f(&self[write], count);
+                write += 1;
+                self.swap(write, read);
+                count = 1;
+            }
+        }
+        f(&self[write], count);
+        write + 1
+    }
+
+    fn position_min(&self) -> Option<usize>
+        where T: Ord
+    {
+        position_best(self, |x| x, |a, b| a < b)
+    }
+
+    fn position_max(&self) -> Option<usize>
+        where T: Ord
+    {
+        position_best(self, |x| x, |a, b| a >= b)
+    }
+
+    fn position_min_by_key<K, F>(&self, key: F) -> Option<usize>
+        where K: Ord,
+              F: FnMut(&T) -> K
+    {
+        position_best(self, key, |a, b| a < b)
+    }
+
+    fn position_max_by_key<K, F>(&self, key: F) -> Option<usize>
+        where K: Ord,
+              F: FnMut(&T) -> K
+    {
+        position_best(self, key, |a, b| a >= b)
+    }
+
+    fn as_array_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        assert!(N != 0, "as_array_chunks: chunk size must not be zero");
+        let n = self.len() / N;
+        let (chunks, rest) = self.split_at(n * N);
+        // [T; N] has the same layout as N consecutive elements
+        let chunks = unsafe {
+            from_raw_parts(chunks.as_ptr() as *const [T; N], n)
+        };
+        (chunks, rest)
+    }
+
+    fn as_array_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        assert!(N != 0, "as_array_chunks_mut: chunk size must not be zero");
+        let n = self.len() / N;
+        let (chunks, rest) = self.split_at_mut(n * N);
+        let chunks = unsafe {
+            from_raw_parts_mut(chunks.as_mut_ptr() as *mut [T; N], n)
+        };
+        (chunks, rest)
+    }
+
+    fn split_n<'a>(&'a self, n: usize) -> BalancedChunks<'a, T> {
+        BalancedChunks::new(self, n)
+    }
+
+    fn split_n_mut<'a>(&'a mut self, n: usize) -> BalancedChunksMut<'a, T> {
+        BalancedChunksMut::new(self, n)
+    }
+
+    fn is_ordered(&self) -> bool
+        where T: PartialOrd
+    {
+        self.is_ordered_by(|a, b| a <= b)
+    }
+
+    fn is_ordered_by<F>(&self, mut in_order: F) -> bool
+        where F: FnMut(&T, &T) -> bool
+    {
+        let len = self.len();
+        let mut i = 1;
+        unsafe {
+            macro_rules! check {
+                ($i:expr) => {
+                    if !in_order(self.get_unchecked($i - 1), self.get_unchecked($i)) {
+                        return false;
+                    }
+                }
+            }
+            while i + 4 <= len {
+                check!(i);
+                check!(i + 1);
+                check!(i + 2);
+                check!(i + 3);
+                i += 4;
+            }
+            while i < len {
+                check!(i);
+                i += 1;
+            }
+        }
+        true
+    }
+
+    fn is_ordered_by_key<K, F>(&self, mut key: F) -> bool
+        where K: PartialOrd,
+              F: FnMut(&T) -> K
+    {
+        let mut iter = self.iter();
+        let mut last = match iter.next() {
+            None => return true,
+            Some(x) => key(x),
+        };
+        for elt in iter {
+            let next = key(elt);
+            if !(last <= next) {
+                return false;
+            }
+            last = next;
+        }
+        true
+    }
+
+    fn select_nth(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+        where T: Ord
+    {
+        self.select_nth_by(index, |a, b| a < b)
+    }
+
+    fn select_nth_by<F>(&mut self, index: usize, mut less_than: F)
+        -> (&mut [T], &mut T, &mut [T])
+        where F: FnMut(&T, &T) -> bool
+    {
+        assert!(index < self.len(), "select_nth: index out of bounds");
+        select_nth_impl(self, index, &mut less_than);
+        let (before, rest) = self.split_at_mut(index);
+        let (nth, after) = rest.split_first_mut().unwrap();
+        (before, nth, after)
+    }
+
+    #[cfg(feature = "std")]
+    fn sort_permutation(&self) -> Vec<usize>
+        where T: Ord
+    {
+        let mut perm = (0..self.len()).collect::<Vec<_>>();
+        perm.sort_by(|&i, &j| self[i].cmp(&self[j]));
+        perm
+    }
+
+    #[cfg(feature = "std")]
+    fn apply_permutation(&mut self, perm: &[usize]) {
+        let len = self.len();
+        assert_eq!(perm.len(), len, "apply_permutation: length mismatch");
+        let mut pending = vec![false; len];
+        for &i in perm {
+            assert!(i < len && !pending[i], "apply_permutation: not a permutation");
+            pending[i] = true;
+        }
+        // follow each cycle of the permutation, swapping elements into place
+        for start in 0..len {
+            let mut i = start;
+            while pending[i] {
+                pending[i] = false;
+                let j = perm[i];
+                if j == start {
+                    break;
+                }
+                self.swap(i, j);
+                i = j;
+            }
+        }
+    }
+
+    fn stable_partition<F>(&mut self, mut pred: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        stable_partition_rec(self, &mut pred)
+    }
+
+    fn stable_partition_buffered<F>(&mut self, mut pred: F, buf: &mut [T]) -> usize
+        where T: Copy,
+              F: FnMut(&T) -> bool
+    {
+        stable_partition_buf(self, &mut pred, buf)
+    }
+
+    fn gallop_left(&self, key: &T) -> usize
+        where T: Ord
+    {
+        gallop(self, |x| x < key)
+    }
+
+    fn gallop_right(&self, key: &T) -> usize
+        where T: Ord
+    {
+        gallop(self, |x| x <= key)
+    }
+
+    fn for_each_window_mut<F>(&mut self, size: usize, f: F)
+        where F: FnMut(&mut [T])
+    {
+        for_each_window_mut(self, size, f)
+    }
+
+    fn for_each_pair_mut<F>(&mut self, f: F)
+        where F: FnMut(&mut T, &mut T)
+    {
+        for_each_pair_mut(self, f)
+    }
+
+    fn trim_start_matches<F>(&self, mut pred: F) -> &[T]
+        where F: FnMut(&T) -> bool
+    {
+        let start = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        &self[start..]
+    }
+
+    fn trim_end_matches<F>(&self, mut pred: F) -> &[T]
+        where F: FnMut(&T) -> bool
+    {
+        let end = self.iter().rposition(|x| !pred(x)).map_or(0, |i| i + 1);
+        &self[..end]
+    }
+
+    fn trim_matches<F>(&self, mut pred: F) -> &[T]
+        where F: FnMut(&T) -> bool
+    {
+        self.trim_start_matches(&mut pred).trim_end_matches(pred)
+    }
+
+    fn without_prefix(&self, prefix: &[T]) -> Option<&[T]>
+        where T: PartialEq
+    {
+        if self.starts_with(prefix) {
+            Some(&self[prefix.len()..])
+        } else {
+            None
+        }
+    }
+
+    fn without_suffix(&self, suffix: &[T]) -> Option<&[T]>
+        where T: PartialEq
+    {
+        if self.ends_with(suffix) {
+            Some(&self[..self.len() - suffix.len()])
+        } else {
+            None
+        }
     }
-    for i in 0..a.len() {
-        if a[i] != b[i] {
-            return offset + i;
+}
+
+#[test]
+fn test_compare_bytes() {
+    let words: &[&[u8]] = &[b"", b"a", b"ab", b"abcdefgh", b"abcdefghi", b"abcdefgi",
+                            b"abcdefgh\xff", b"b", b"\xffabcdefgh", b"abcdefghabcdefgz",
+                            b"abcdefghabcdefgza"];
+    for a in words {
+        for b in words {
+            assert_eq!(compare_bytes(a, b), a.cmp(b), "{:?} {:?}", a, b);
         }
     }
-    len
 }
 
-/// Rotate `steps` towards lower indices.
-///
-/// The steps to rotate is computed modulo the length of `data`,
-/// so any step value is acceptable. This function does not panic.
-///
-/// ```
-/// use odds::slice::rotate_left;
-///
-/// let mut data = [1, 2, 3, 4];
-/// rotate_left(&mut data, 1);
-/// assert_eq!(&data, &[2, 3, 4, 1]);
-/// rotate_left(&mut data, 2);
-/// assert_eq!(&data, &[4, 1, 2, 3]);
-/// ```
-pub fn rotate_left<T>(data: &mut [T], steps: usize) {
-    //return rotate_alt(data, steps);
-    // no bounds checks in this method in this version
-    if data.len() == 0 {
-        return;
+#[test]
+fn test_constant_time_eq() {
+    let a = [0x5au8; 33];
+    for i in 0..a.len() {
+        let mut b = a;
+        assert!(constant_time_eq(&a, &b));
+        b[i] ^= 0x80;
+        assert!(!constant_time_eq(&a, &b));
     }
-    let steps = steps % data.len();
+    assert!(constant_time_eq(&[], &[]));
+    assert!(!constant_time_eq(&a[..1], &[]));
+}
 
-    data[..steps].reverse();
-    data[steps..].reverse();
-    data.reverse();
+#[test]
+fn test_swap_ranges() {
+    let mut a = (0..100).map(|x| x.to_string()).collect::<Vec<_>>();
+    let mut b = (100..200).map(|x| x.to_string()).collect::<Vec<_>>();
+    swap_ranges(&mut a[10..60], &mut b[..50]);
+    assert_eq!(a[9], "9");
+    assert_eq!(a[10], "100");
+    assert_eq!(a[59], "149");
+    assert_eq!(b[0], "10");
+    assert_eq!(b[49], "59");
+    assert_eq!(b[50], "150");
+    swap_ranges(&mut a[..0], &mut b[..0]);
 }
 
 #[test]
-fn test_shared_prefix() {
-    let mut a = [0xff; 256];
-    let b = [0xff; 256];
-    for byte in 0..255 { // don't test byte 255
-        for i in 0..a.len() {
-            a[i] = byte;
-            let ans = shared_prefix(&a, &b);
-            assert!(ans == i, "failed for index {} and byte {:x} (got ans={})",
-                    i, byte, ans);
-            a[i] = 0xff;
+#[should_panic]
+fn test_swap_ranges_len() {
+    swap_ranges(&mut [1, 2], &mut [3]);
+}
+
+#[test]
+fn test_unchecked_access() {
+    let mut data = [0, 1, 2, 3, 4];
+    unsafe {
+        SliceExt::swap_unchecked(&mut data[..], 1, 3);
+        SliceExt::swap_unchecked(&mut data[..], 2, 2);
+        assert_eq!(data, [0, 3, 2, 1, 4]);
+        assert_eq!(data.get_unchecked_range(..), &data[..]);
+        assert_eq!(data.get_unchecked_range(..2), &[0, 3]);
+        assert_eq!(data.get_unchecked_range(5..), &[]);
+        data.get_unchecked_range_mut(3..).reverse();
+    }
+    assert_eq!(data, [0, 3, 2, 4, 1]);
+}
+
+#[test]
+fn test_shuffle() {
+    use random::XorShift;
+
+    let mut rng = XorShift::new(1);
+    // every permutation of three elements should show up
+    let mut seen = [0; 6];
+    for _ in 0..600 {
+        let mut data = [0, 1, 2];
+        data.shuffle(&mut rng);
+        let mut sorted = data;
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2]);
+        let perm = data[0] * 2 + (data[1] > data[2]) as usize;
+        seen[perm] += 1;
+    }
+    assert!(seen.iter().all(|&n| n > 50), "{:?}", seen);
+    let mut empty: [u8; 0] = [];
+    empty.shuffle(&mut rng);
+}
+
+#[test]
+#[should_panic]
+fn test_shuffle_bad_below() {
+    struct Bad;
+    impl RandomSource for Bad {
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+        fn below(&mut self, _n: usize) -> usize {
+            1_000_000
         }
     }
+    [1u8, 2, 3].shuffle(&mut Bad);
 }
 
-/// Element-finding methods for slices
-pub trait SliceFind {
-    type Item;
-    /// Linear search for the first occurrence  `elt` in the slice.
-    ///
-    /// Return its index if it is found, or None.
-    fn find<U: ?Sized>(&self, elt: &U) -> Option<usize>
-        where Self::Item: PartialEq<U>;
+#[test]
+fn test_partition_in_place() {
+    use random::{RandomSource, XorShift};
 
-    /// Linear search for the last occurrence  `elt` in the slice.
-    ///
-    /// Return its index if it is found, or None.
-    fn rfind<U: ?Sized>(&self, elt: &U) -> Option<usize>
-        where Self::Item: PartialEq<U>;
+    let mut rng = XorShift::new(3);
+    for len in 0..40 {
+        let mut data = (0..len).map(|_| rng.below(10)).collect::<Vec<_>>();
+        let mut expected = data.clone();
+        let mut calls = 0;
+        let mid = data.partition_in_place(|&x| { calls += 1; x % 3 == 0 });
+        assert_eq!(calls, len);
+        assert!(data[..mid].iter().all(|&x| x % 3 == 0));
+        assert!(data[mid..].iter().all(|&x| x % 3 != 0));
+        data.sort();
+        expected.sort();
+        assert_eq!(data, expected);
+    }
 }
 
-macro_rules! foreach {
-    ($i:pat in $($e:expr),* => $b:expr) => {{
-        $(
-            let $i = $e;
-            $b;
-        )*
-    }}
+#[test]
+fn test_dedup_with_count() {
+    let mut data = [1, 1, 2, 3, 3, 3, 1, 4, 4];
+    let mut runs = Vec::new();
+    let n = data.dedup_with_count(|&x, count| runs.push((x, count)));
+    assert_eq!(&data[..n], &[1, 2, 3, 1, 4]);
+    assert_eq!(runs, [(1, 2), (2, 1), (3, 3), (1, 1), (4, 2)]);
+
+    let mut words = vec![String::from("a"), String::from("a"), String::from("b")];
+    let n = words.dedup_with_count(|_, _| ());
+    words.truncate(n);
+    assert_eq!(words, ["a", "b"]);
+
+    let mut empty: [u8; 0] = [];
+    assert_eq!(empty.dedup_with_count(|_, _| panic!()), 0);
 }
 
-impl<T> SliceFind for [T] { 
-    type Item = T;
-    fn find<U: ?Sized>(&self, elt: &U) -> Option<usize>
-        where Self::Item: PartialEq<U>
-    {
-        SliceIter::from(self).position(move |x| *x == *elt)
+#[test]
+fn test_position_min_max() {
+    let data = [5, 3, 9, 3, 0, 9, 9, 2, 0, 1];
+    for len in 0..data.len() + 1 {
+        let s = &data[..len];
+        let min = s.iter().enumerate().min_by_key(|p| p.1).map(|p| p.0);
+        let max = s.iter().enumerate().max_by_key(|p| p.1).map(|p| p.0);
+        assert_eq!(s.position_min(), min);
+        assert_eq!(s.position_max(), max);
+        let mut calls = 0;
+        let rev_min = s.position_max_by_key(|&x| { calls += 1; -x });
+        assert_eq!(calls, len);
+        assert_eq!(s.position_min_by_key(|&x| -x), s.iter().enumerate()
+                   .min_by_key(|p| -p.1).map(|p| p.0));
+        if len > 0 {
+            assert_eq!(s[rev_min.unwrap()], s[min.unwrap()]);
+        }
     }
+}
 
-    fn rfind<U: ?Sized>(&self, elt: &U) -> Option<usize>
-        where Self::Item: PartialEq<U>
+#[test]
+fn test_as_array_chunks() {
+    let mut data = [0u16; 10];
     {
-        SliceIter::from(self).rposition(move |x| *x == *elt)
+        let (chunks, rest) = data.as_array_chunks_mut::<4>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(rest.len(), 2);
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            chunk[3] = i as u16 + 1;
+        }
+        rest[0] = 9;
     }
+    assert_eq!(data, [0, 0, 0, 1, 0, 0, 0, 2, 9, 0]);
+    let (chunks, rest) = data.as_array_chunks::<5>();
+    assert_eq!(chunks, &[[0, 0, 0, 1, 0], [0, 0, 2, 9, 0]]);
+    assert_eq!(rest, &[]);
+    let (chunks, rest) = data.as_array_chunks::<11>();
+    assert_eq!(chunks.len(), 0);
+    assert_eq!(rest, &data[..]);
 }
 
-/// Element-finding methods for slices
-pub trait SliceFindSplit {
-    type Item;
-    /// Linear search for the first occurrence  `elt` in the slice.
-    ///
-    /// Return the part before and the part including and after the element.
-    /// If the element is not found, the second half is empty.
-    fn find_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
-        where Self::Item: PartialEq<U>;
+#[test]
+fn test_is_ordered() {
+    let mut data = [0, 1, 1, 2, 3, 5, 8, 13, 21];
+    for len in 0..data.len() + 1 {
+        assert!(data[..len].is_ordered());
+        assert!(data[..len].is_ordered_by_key(|&x| x / 2));
+    }
+    for i in 1..data.len() {
+        data.swap(i - 1, i);
+        let sorted = data[i - 1] == data[i];
+        assert_eq!(data.is_ordered(), sorted);
+        assert_eq!(data.is_ordered_by(|a, b| a <= b), sorted);
+        data.swap(i - 1, i);
+    }
+    assert!(![1., ::std::f64::NAN].is_ordered());
+    assert!([3, 2, 1].is_ordered_by(|a, b| a > b));
+}
 
-    /// Linear search for the last occurrence  `elt` in the slice.
-    ///
-    /// Return the part before and the part including and after the element.
-    /// If the element is not found, the first half is empty.
-    fn rfind_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
-        where Self::Item: PartialEq<U>;
+#[test]
+fn test_select_nth() {
+    use random::{RandomSource, XorShift};
 
-    /// Linear search for the first occurrence  `elt` in the slice.
-    ///
-    /// Return the part before and the part including and after the element.
-    /// If the element is not found, the second half is empty.
-    fn find_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
-        where Self::Item: PartialEq<U>;
+    let mut rng = XorShift::new(5);
+    for &len in &[1, 2, 7, 11, 50, 201] {
+        for &range in &[2, 10, 1000] {
+            let data = (0..len).map(|_| rng.below(range)).collect::<Vec<_>>();
+            let mut sorted = data.clone();
+            sorted.sort();
+            for k in 0..len {
+                let mut v = data.clone();
+                {
+                    let (before, nth, after) = v.select_nth(k);
+                    assert_eq!(*nth, sorted[k]);
+                    assert!(before.iter().all(|x| x <= &sorted[k]));
+                    assert!(after.iter().all(|x| x >= &sorted[k]));
+                }
+                v.sort();
+                assert_eq!(v, sorted);
+            }
+        }
+    }
 
-    /// Linear search for the last occurrence  `elt` in the slice.
-    ///
-    /// Return the part before and the part including and after the element.
-    /// If the element is not found, the first half is empty.
-    fn rfind_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
-        where Self::Item: PartialEq<U>;
+    // median-of-medians alone must also be correct
+    let mut v = (0..100).rev().collect::<Vec<_>>();
+    let m = median_of_medians(&mut v, &mut |a, b| a < b);
+    let pivot = v[m];
+    assert!(pivot >= 30 && pivot < 70);
+
+    let mut v = [3, 1, 2];
+    let (_, max, _) = v.select_nth_by(0, |a, b| a > b);
+    assert_eq!(*max, 3);
 }
 
+#[test]
+fn test_permutation() {
+    use random::{RandomSource, XorShift};
 
-/// Unchecked version of `xs.split_at(i)`.
-unsafe fn split_at_unchecked<T>(xs: &[T], i: usize) -> (&[T], &[T]) {
-    (slice_unchecked(xs, 0, i),
-     slice_unchecked(xs, i, xs.len()))
+    let mut rng = XorShift::new(11);
+    for len in 0..30 {
+        let keys = (0..len).map(|_| rng.below(5)).collect::<Vec<_>>();
+        let perm = keys.sort_permutation();
+        let mut sorted = keys.iter().cloned().zip(0..len).collect::<Vec<_>>();
+        sorted.sort();
+        assert_eq!(perm, sorted.iter().map(|x| x.1).collect::<Vec<_>>());
+
+        let mut indices = (0..len).collect::<Vec<_>>();
+        indices.apply_permutation(&perm);
+        assert_eq!(indices, perm);
+        let mut keys = keys;
+        keys.apply_permutation(&perm);
+        assert!(keys.is_ordered());
+    }
 }
 
-impl<T> SliceFindSplit for [T] { 
-    type Item = T;
-    fn find_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
-        where Self::Item: PartialEq<U>
-    {
-        let i = self.find(elt).unwrap_or(self.len());
-        unsafe {
-            split_at_unchecked(self, i)
+#[test]
+#[should_panic]
+fn test_apply_permutation_invalid() {
+    [1, 2, 3].apply_permutation(&[0, 2, 2]);
+}
+
+#[test]
+fn test_stable_partition() {
+    use random::{RandomSource, XorShift};
+
+    let mut rng = XorShift::new(19);
+    for len in 0..50 {
+        let data = (0..len).map(|i| (rng.below(3), i)).collect::<Vec<_>>();
+        let mut expected = data.iter().cloned().filter(|x| x.0 == 0).collect::<Vec<_>>();
+        let n = expected.len();
+        expected.extend(data.iter().cloned().filter(|x| x.0 != 0));
+
+        let mut v = data.clone();
+        let mut calls = 0;
+        assert_eq!(v.stable_partition(|x| { calls += 1; x.0 == 0 }), n);
+        assert_eq!(calls, len);
+        assert_eq!(v, expected);
+        for &buf_len in &[0, 1, 7, 50] {
+            let mut buf = vec![(0, 0); buf_len];
+            let mut v = data.clone();
+            assert_eq!(v.stable_partition_buffered(|x| x.0 == 0, &mut buf), n);
+            assert_eq!(v, expected);
         }
     }
+}
 
-    fn find_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
-        where Self::Item: PartialEq<U>
-    {
-        let i = self.find(elt).unwrap_or(self.len());
-        self.split_at_mut(i)
+#[test]
+fn test_gallop() {
+    let data = [0, 1, 1, 3, 3, 3, 3, 3, 4, 7, 7, 9, 10, 10, 10, 10, 12];
+    for len in 0..data.len() + 1 {
+        let s = &data[..len];
+        for key in -1..14 {
+            assert_eq!(s.gallop_left(&key), s.iter().filter(|&&x| x < key).count());
+            assert_eq!(s.gallop_right(&key), s.iter().filter(|&&x| x <= key).count());
+        }
     }
+}
 
-    fn rfind_split<U: ?Sized>(&self, elt: &U) -> (&Self, &Self)
-        where Self::Item: PartialEq<U>
+#[test]
+fn test_get_many_mut() {
+    let mut data = [0; 5];
     {
-        let i = self.rfind(elt).unwrap_or(0);
-        unsafe {
-            split_at_unchecked(self, i)
-        }
+        let [a, b, c] = SliceExt::get_many_mut(&mut data[..], [4, 0, 2]).unwrap();
+        *a = 1;
+        *b = 2;
+        *c = 3;
     }
+    assert_eq!(data, [2, 0, 3, 0, 1]);
+    assert!(SliceExt::get_many_mut(&mut data[..], [0, 2, 0]).is_none());
+    assert!(SliceExt::get_many_mut(&mut data[..], [5]).is_none());
+    assert_eq!(SliceExt::get_many_mut(&mut data[..], []), Some([]));
+    assert!(SliceExt::get_many_mut(&mut [0; 0][..], [0]).is_none());
 
-    fn rfind_split_mut<U: ?Sized>(&mut self, elt: &U) -> (&mut Self, &mut Self)
-        where Self::Item: PartialEq<U>
-    {
-        let i = self.rfind(elt).unwrap_or(0);
-        self.split_at_mut(i)
+    let mut data = [1, 2, 3];
+    data.rotate_left_mod(4);
+    assert_eq!(data, [2, 3, 1]);
+    data.rotate_right_mod(2);
+    assert_eq!(data, [3, 1, 2]);
+}
+
+#[test]
+fn test_bounds() {
+    let data = [1, 3, 3, 5, 8, 8, 8, 13];
+    for x in 0..15 {
+        let lower = data.iter().take_while(|&&y| y < x).count();
+        let upper = data.iter().take_while(|&&y| y <= x).count();
+        assert_eq!(data.lower_bound_by(|y| y.cmp(&x)), lower);
+        assert_eq!(data.upper_bound_by(|y| y.cmp(&x)), upper);
+        assert_eq!(data.equal_range_by(|y| y.cmp(&x)), lower..upper);
     }
+    let empty: [i32; 0] = [];
+    assert_eq!(empty.equal_range_by(|y| y.cmp(&1)), 0..0);
+}
+
+#[test]
+fn test_fill() {
+    let mut bytes = [0i8; 37];
+    bytes.fill_byte(-3);
+    assert!(bytes.iter().all(|&x| x == -3));
+    let mut flags = [false; 5];
+    flags[..3].fill_byte(true);
+    assert_eq!(flags, [true, true, true, false, false]);
+    let mut words = [0u32; 5];
+    words[1..].fill_copy(0x01020304);
+    assert_eq!(words, [0, 0x01020304, 0x01020304, 0x01020304, 0x01020304]);
+    let mut strings = vec![String::new(); 3];
+    strings.fill_with_fn(|| "x".to_string());
+    assert_eq!(strings, ["x", "x", "x"]);
 }
 
+#[test]
+fn test_copy_within_range() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    data.copy_within_range(1..4, 2);
+    assert_eq!(data, [0, 1, 1, 2, 3, 5]);
+    data.copy_within_range(3.., 0);
+    assert_eq!(data, [2, 3, 5, 2, 3, 5]);
+    data.copy_within_range(..2, 4);
+    assert_eq!(data, [2, 3, 5, 2, 2, 3]);
+    data.copy_within_range(.., 0);
+    data.copy_within_range(6.., 6);
+    assert_eq!(data, [2, 3, 5, 2, 2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn test_copy_within_range_out_of_bounds() {
+    let mut data = [0, 1, 2, 3];
+    data.copy_within_range(1..3, 3);
+}
 
 /// Extra iterator adaptors for iterators of slice elements.
 pub trait SliceIterExt : Iterator {
@@ -237,13 +2578,96 @@ pub trait SliceIterExt : Iterator {
     {
         MendSlices::new(self)
     }
+
+    /// Return an iterator adaptor that skips consecutive equal elements,
+    /// yielding only the first element of each run.
+    ///
+    /// ```
+    /// use odds::slice::SliceIterExt;
+    /// use odds::slice::iter::SliceCopyIter;
+    ///
+    /// let data = [1, 1, 2, 2, 2, 3, 1];
+    /// let iter = SliceCopyIter::from(&data[..]).dedup();
+    /// assert!(iter.eq(vec![1, 2, 3, 1]));
+    /// ```
+    fn dedup(self) -> DedupIter<Self, fn(&Self::Item, &Self::Item) -> bool>
+        where Self: Sized,
+              Self::Item: PartialEq
+    {
+        DedupIter::new(self, PartialEq::eq)
+    }
+
+    /// Return an iterator adaptor that skips consecutive elements for which
+    /// `same(&first, &next)` returns `true`, where `first` is the first
+    /// element of the current run. Only the first element of each run is
+    /// yielded.
+    ///
+    /// ```
+    /// use odds::slice::SliceIterExt;
+    ///
+    /// let words = ["apple", "avocado", "banana", "blueberry", "cherry"];
+    /// let iter = words.iter().dedup_by(|a, b| a[..1] == b[..1]);
+    /// assert!(iter.eq(&["apple", "banana", "cherry"]));
+    /// ```
+    fn dedup_by<F>(self, same: F) -> DedupIter<Self, F>
+        where Self: Sized,
+              F: FnMut(&Self::Item, &Self::Item) -> bool
+    {
+        DedupIter::new(self, same)
+    }
+
+    /// Return an iterator adaptor that yields `(Position, element)` pairs,
+    /// where the position tells if the element is the first, last, only,
+    /// or one of the middle elements.
+    ///
+    /// ```
+    /// use odds::slice::SliceIterExt;
+    /// use odds::slice::iter::Position;
+    ///
+    /// let words = ["red", "green", "blue"];
+    /// let mut s = String::new();
+    /// for (pos, word) in words.iter().with_position() {
+    ///     s.push_str(word);
+    ///     match pos {
+    ///         Position::First | Position::Middle => s.push_str(", "),
+    ///         Position::Last | Position::Only => s.push('.'),
+    ///     }
+    /// }
+    /// assert_eq!(s, "red, green, blue.");
+    /// ```
+    fn with_position(self) -> WithPosition<Self>
+        where Self: Sized + ExactSizeIterator
+    {
+        WithPosition::new(self)
+    }
+
+    /// Return an iterator adaptor that counts the elements consumed from it.
+    ///
+    /// ```
+    /// use odds::slice::SliceIterExt;
+    ///
+    /// let data = [0u8; 1000];
+    /// let mut iter = data.iter().counted();
+    /// let mut progress = Vec::new();
+    /// while let Some(_) = iter.next() {
+    ///     if iter.consumed() % 250 == 0 {
+    ///         progress.push(iter.consumed());
+    ///     }
+    /// }
+    /// assert_eq!(progress, vec![250, 500, 750, 1000]);
+    /// ```
+    fn counted(self) -> Counted<Self>
+        where Self: Sized + ExactSizeIterator
+    {
+        Counted::new(self)
+    }
 }
 
 impl<I: ?Sized> SliceIterExt for I where I: Iterator { }
 
 /// An iterator adaptor that glues together adjacent contiguous slices.
 ///
-/// See [`.mend_slices()`](../trait.Itertools.html#method.mend_slices) for more information.
+/// See [`.mend_slices()`](trait.SliceIterExt.html#method.mend_slices) for more information.
 pub struct MendSlices<I>
     where I: Iterator
 {
@@ -301,7 +2725,10 @@ impl<I> Iterator for MendSlices<I>
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        // any number of the remaining slices may be mended together
+        let extra = self.last.is_some() as usize;
+        let (_, hi) = self.iter.size_hint();
+        (extra, hi.and_then(|x| x.checked_add(extra)))
     }
 }
 
@@ -318,7 +2745,7 @@ impl<'a, T> MendSlice for &'a [T] {
     #[inline]
     fn mend(a: Self, b: Self) -> Result<Self, (Self, Self)> {
         unsafe {
-            let a_end = a.as_ptr().offset(a.len() as isize);
+            let a_end = a.as_ptr().debug_add(a.len());
             if a_end == b.as_ptr() {
                 Ok(::std::slice::from_raw_parts(a.as_ptr(), a.len() + b.len()))
             } else {
@@ -332,7 +2759,7 @@ impl<'a, T> MendSlice for &'a mut [T] {
     #[inline]
     fn mend(a: Self, b: Self) -> Result<Self, (Self, Self)> {
         unsafe {
-            let a_end = a.as_ptr().offset(a.len() as isize);
+            let a_end = a.as_ptr().debug_add(a.len());
             if a_end == b.as_ptr() {
                 Ok(::std::slice::from_raw_parts_mut(a.as_mut_ptr(), a.len() + b.len()))
             } else {
@@ -368,6 +2795,28 @@ macro_rules! impl_pod {
 impl_pod!{u8 u16 u32 u64 usize i8 i16 i32 i64 isize}
 impl_pod!{@array 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16}
 
+/// Types that are a single, fully initialized byte, so that a slice of them
+/// can be set with `memset`. Used by `SliceExt::fill_byte`.
+pub unsafe trait ByteValue : Copy {
+    #[doc(hidden)]
+    fn to_byte(self) -> u8;
+}
+
+unsafe impl ByteValue for u8 {
+    #[inline(always)]
+    fn to_byte(self) -> u8 { self }
+}
+
+unsafe impl ByteValue for i8 {
+    #[inline(always)]
+    fn to_byte(self) -> u8 { self as u8 }
+}
+
+unsafe impl ByteValue for bool {
+    #[inline(always)]
+    fn to_byte(self) -> u8 { self as u8 }
+}
+
 
 /// Split the input slice into three chunks,
 /// so that the middle chunk is a slice of a larger "block size"
@@ -410,8 +2859,8 @@ pub fn split_aligned_for<T: Pod>(data: &[u8]) -> (&[u8], &[T], &[u8]) {
     }
     unsafe {
         (from_raw_parts(ptr, prefix),
-         from_raw_parts(ptr.offset(prefix as isize) as *const T, t_len),
-         from_raw_parts(ptr.offset((prefix + t_len * size_t) as isize),
+         from_raw_parts(ptr.debug_add(prefix) as *const T, t_len),
+         from_raw_parts(ptr.debug_add(prefix + t_len * size_t),
                         data.len() - t_len * size_t - prefix))
     }
 }
@@ -586,3 +3035,84 @@ fn test_find() {
     assert_eq!(v.rfind_split(&2), v.split_at(v.len() - 2));
 }
 
+
+/// Call `f` with each window of `size` adjacent elements of `data`, front to
+/// back, as a mutable slice.
+///
+/// The windows overlap, which is why this takes a closure instead of
+/// returning an iterator; changes made in one call are visible in the next.
+/// If `data` is shorter than `size`, `f` is not called.
+///
+/// **Panics** if `size` is zero.
+///
+/// ```
+/// use odds::slice::for_each_window_mut;
+///
+/// // smooth in place: replace each middle element by the mean of its window
+/// let mut data = [0., 3., 0., 3., 0.];
+/// for_each_window_mut(&mut data, 3, |w| w[1] = (w[0] + w[1] + w[2]) / 3.);
+/// assert_eq!(data, [0., 1., 4. / 3., 13. / 9., 0.]);
+/// ```
+pub fn for_each_window_mut<T, F>(data: &mut [T], size: usize, mut f: F)
+    where F: FnMut(&mut [T])
+{
+    assert!(size != 0, "for_each_window_mut: window size must not be zero");
+    if data.len() < size {
+        return;
+    }
+    for i in 0..data.len() - size + 1 {
+        f(&mut data[i..i + size]);
+    }
+}
+
+#[test]
+fn test_for_each_pair_mut() {
+    let mut data = [5, 3, 4, 1];
+    let mut n = 0;
+    for_each_pair_mut(&mut data, |a, b| {
+        n += 1;
+        if *a > *b {
+            mem::swap(a, b);
+        }
+    });
+    assert_eq!(n, 3);
+    assert_eq!(data, [3, 4, 1, 5]);
+
+    let mut single = [1];
+    for_each_pair_mut(&mut single, |_, _| panic!());
+    let mut empty: [i32; 0] = [];
+    for_each_pair_mut(&mut empty, |_, _| panic!());
+}
+
+#[test]
+fn test_trim_strip() {
+    let data = [1, 1, 2, 1, 3, 1];
+    let is_one = |&x: &i32| x == 1;
+    assert_eq!(data.trim_start_matches(is_one), &[2, 1, 3, 1]);
+    assert_eq!(data.trim_end_matches(is_one), &[1, 1, 2, 1, 3]);
+    assert_eq!(data.trim_matches(is_one), &[2, 1, 3]);
+    assert_eq!(data.trim_matches(|_| true), &[]);
+    assert_eq!(data[..0].trim_end_matches(|_| false), &[]);
+
+    assert_eq!(data.without_prefix(&[]), Some(&data[..]));
+    assert_eq!(data.without_prefix(&data), Some(&[][..]));
+    assert_eq!(data.without_prefix(&[1, 2]), None);
+    assert_eq!(data.without_suffix(&[3, 1]), Some(&data[..4]));
+    assert_eq!(data[..2].without_suffix(&data), None);
+}
+
+#[test]
+fn test_for_each_window_mut() {
+    let mut data = [1, 0, 0, 0, 0, 0];
+    let mut calls = 0;
+    data.for_each_window_mut(3, |w| {
+        calls += 1;
+        assert_eq!(w.len(), 3);
+        w[2] = w[0] + w[1] + 1;
+    });
+    assert_eq!(calls, 4);
+    assert_eq!(data, [1, 0, 2, 3, 6, 10]);
+    data.for_each_window_mut(7, |_| panic!());
+    data.for_each_window_mut(6, |w| w.reverse());
+    assert_eq!(data, [10, 6, 3, 2, 0, 1]);
+}
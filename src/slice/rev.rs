@@ -1,5 +1,6 @@
 //! A reversed view of a slice.
 
+use std::cmp::Ordering;
 use std::hash::{Hasher, Hash};
 use std::mem::transmute;
 use std::iter::Rev;
@@ -50,6 +51,11 @@ impl<T> RevSlice<T> {
         self.0.len()
     }
 
+    /// Return `true` if the slice has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     // arithmetic overflow checked in debug builds
     #[inline]
     fn raw_index_no_wrap(&self, i: usize) -> usize {
@@ -148,6 +154,36 @@ impl<T, U> PartialEq<[U]> for RevSlice<T>
     }
 }
 
+/// `RevSlice` orders lexicographically by logical element sequence.
+impl<T> PartialOrd for RevSlice<T>
+    where T: PartialOrd,
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        for (x, y) in self.into_iter().zip(rhs) {
+            match x.partial_cmp(y) {
+                Some(Ordering::Equal) => { }
+                non_eq => return non_eq,
+            }
+        }
+        self.len().partial_cmp(&rhs.len())
+    }
+}
+
+/// `RevSlice` orders lexicographically by logical element sequence.
+impl<T> Ord for RevSlice<T>
+    where T: Ord,
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        for (x, y) in self.into_iter().zip(rhs) {
+            match x.cmp(y) {
+                Ordering::Equal => { }
+                non_eq => return non_eq,
+            }
+        }
+        self.len().cmp(&rhs.len())
+    }
+}
+
 impl<T> Hash for RevSlice<T>
     where T: Hash,
 {
@@ -385,3 +421,21 @@ fn test_rev_slice_hash() {
         }
     }
 }
+
+#[test]
+fn test_rev_slice_ord() {
+    let data = [1, 2, 3, 4];
+    let rev = [4, 3, 2, 1];
+
+    let r = <&RevSlice<_>>::from(&data[..]);
+    let s = <&RevSlice<_>>::from(&rev[..]);
+    for i in 0..r.len() {
+        for j in i..r.len() {
+            for k in 0..s.len() {
+                assert_eq!(r[i..j].cmp(&s[k..]), rev[i..j].cmp(&data[k..]));
+                assert_eq!(r[i..j].partial_cmp(&s[k..]), rev[i..j].partial_cmp(&data[k..]));
+            }
+        }
+    }
+    assert!(r[..0].is_empty());
+}
@@ -0,0 +1,317 @@
+//! In-place prefix sums and differences for numeric slices
+
+/// Primitive numeric types, for the prefix sum and difference functions.
+pub trait Num : Copy {
+    #[doc(hidden)]
+    fn add(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn sub(self, rhs: Self) -> Self;
+}
+
+/// Primitive integer types, for the wrapping and saturating variants of the
+/// prefix sum and difference functions.
+pub trait Int : Num {
+    #[doc(hidden)]
+    fn wrapping_add(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn saturating_add(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_num {
+    (@int $($t:ty)+) => {
+        $(
+        impl_num!($t);
+        impl Int for $t {
+            #[inline(always)]
+            fn wrapping_add(self, rhs: Self) -> Self { <$t>::wrapping_add(self, rhs) }
+            #[inline(always)]
+            fn wrapping_sub(self, rhs: Self) -> Self { <$t>::wrapping_sub(self, rhs) }
+            #[inline(always)]
+            fn saturating_add(self, rhs: Self) -> Self { <$t>::saturating_add(self, rhs) }
+            #[inline(always)]
+            fn saturating_sub(self, rhs: Self) -> Self { <$t>::saturating_sub(self, rhs) }
+        }
+        )+
+    };
+    ($($t:ty)+) => {
+        $(
+        impl Num for $t {
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self { self + rhs }
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self { self - rhs }
+        }
+        )+
+    };
+}
+impl_num!{@int u8 u16 u32 u64 usize i8 i16 i32 i64 isize}
+impl_num!{f32 f64}
+
+/// Replace each element by `f(previous result, element)`, front to back.
+#[inline]
+fn scan_forward<T, F>(data: &mut [T], mut f: F)
+    where T: Copy,
+          F: FnMut(T, T) -> T,
+{
+    if data.len() == 0 {
+        return;
+    }
+    let mut acc = data[0];
+    let mut tail = &mut data[1..];
+    while tail.len() >= 4 {
+        acc = f(acc, tail[0]);
+        tail[0] = acc;
+        acc = f(acc, tail[1]);
+        tail[1] = acc;
+        acc = f(acc, tail[2]);
+        tail[2] = acc;
+        acc = f(acc, tail[3]);
+        tail[3] = acc;
+        tail = &mut {tail}[4..];
+    }
+    for elt in tail {
+        acc = f(acc, *elt);
+        *elt = acc;
+    }
+}
+
+/// Replace each element by `f(previous result, element)`, back to front.
+#[inline]
+fn scan_backward<T, F>(data: &mut [T], mut f: F)
+    where T: Copy,
+          F: FnMut(T, T) -> T,
+{
+    let len = data.len();
+    if len == 0 {
+        return;
+    }
+    let mut acc = data[len - 1];
+    let mut head = &mut data[..len - 1];
+    while head.len() >= 4 {
+        let n = head.len();
+        acc = f(acc, head[n - 1]);
+        head[n - 1] = acc;
+        acc = f(acc, head[n - 2]);
+        head[n - 2] = acc;
+        acc = f(acc, head[n - 3]);
+        head[n - 3] = acc;
+        acc = f(acc, head[n - 4]);
+        head[n - 4] = acc;
+        head = &mut {head}[..n - 4];
+    }
+    for elt in head.iter_mut().rev() {
+        acc = f(acc, *elt);
+        *elt = acc;
+    }
+}
+
+/// Replace each element (except the first) by its difference from the
+/// element before it, `f(element, previous element)`.
+#[inline]
+fn difference<T, F>(data: &mut [T], mut f: F)
+    where T: Copy,
+          F: FnMut(T, T) -> T,
+{
+    // go back to front so that each previous element is still unchanged
+    let mut i = data.len();
+    while i >= 5 {
+        data[i - 1] = f(data[i - 1], data[i - 2]);
+        data[i - 2] = f(data[i - 2], data[i - 3]);
+        data[i - 3] = f(data[i - 3], data[i - 4]);
+        data[i - 4] = f(data[i - 4], data[i - 5]);
+        i -= 4;
+    }
+    while i >= 2 {
+        data[i - 1] = f(data[i - 1], data[i - 2]);
+        i -= 1;
+    }
+}
+
+/// Replace each element by `f(previous result, element)`, front to back;
+/// the first element is unchanged.
+///
+/// This is the general form of `prefix_sum_in_place`.
+///
+/// ```
+/// use odds::slice::scan::scan_in_place;
+///
+/// // running maximum
+/// let mut data = [3, 1, 4, 1, 5, 9, 2, 6];
+/// scan_in_place(&mut data, |a, b| if a > b { a } else { b });
+/// assert_eq!(data, [3, 3, 4, 4, 5, 9, 9, 9]);
+/// ```
+pub fn scan_in_place<T, F>(data: &mut [T], f: F)
+    where T: Copy,
+          F: FnMut(T, T) -> T,
+{
+    scan_forward(data, f)
+}
+
+/// Replace each element by `f(next result, element)`, back to front; the
+/// last element is unchanged.
+///
+/// This is the general form of `suffix_sum_in_place`.
+pub fn scan_in_place_rev<T, F>(data: &mut [T], f: F)
+    where T: Copy,
+          F: FnMut(T, T) -> T,
+{
+    scan_backward(data, f)
+}
+
+/// Replace each element by the sum of itself and all elements before it.
+///
+/// Overflow behaves like the `+` operator.
+///
+/// ```
+/// use odds::slice::scan::prefix_sum_in_place;
+///
+/// let mut data = [1, 2, 3, 4, 5];
+/// prefix_sum_in_place(&mut data);
+/// assert_eq!(data, [1, 3, 6, 10, 15]);
+/// ```
+pub fn prefix_sum_in_place<T: Num>(data: &mut [T]) {
+    scan_forward(data, Num::add)
+}
+
+/// Replace each element by the sum of itself and all elements before it,
+/// wrapping around on overflow.
+pub fn wrapping_prefix_sum_in_place<T: Int>(data: &mut [T]) {
+    scan_forward(data, Int::wrapping_add)
+}
+
+/// Replace each element by the sum of itself and all elements before it,
+/// saturating at the numeric bounds.
+///
+/// ```
+/// use odds::slice::scan::saturating_prefix_sum_in_place;
+///
+/// let mut data = [100u8, 100, 100, 0];
+/// saturating_prefix_sum_in_place(&mut data);
+/// assert_eq!(data, [100, 200, 255, 255]);
+/// ```
+pub fn saturating_prefix_sum_in_place<T: Int>(data: &mut [T]) {
+    scan_forward(data, Int::saturating_add)
+}
+
+/// Replace each element by the sum of itself and all elements after it.
+///
+/// Overflow behaves like the `+` operator.
+///
+/// ```
+/// use odds::slice::scan::suffix_sum_in_place;
+///
+/// let mut data = [1, 2, 3, 4, 5];
+/// suffix_sum_in_place(&mut data);
+/// assert_eq!(data, [15, 14, 12, 9, 5]);
+/// ```
+pub fn suffix_sum_in_place<T: Num>(data: &mut [T]) {
+    scan_backward(data, Num::add)
+}
+
+/// Replace each element by the sum of itself and all elements after it,
+/// wrapping around on overflow.
+pub fn wrapping_suffix_sum_in_place<T: Int>(data: &mut [T]) {
+    scan_backward(data, Int::wrapping_add)
+}
+
+/// Replace each element by the sum of itself and all elements after it,
+/// saturating at the numeric bounds.
+pub fn saturating_suffix_sum_in_place<T: Int>(data: &mut [T]) {
+    scan_backward(data, Int::saturating_add)
+}
+
+/// Replace each element, except the first, by its difference from the
+/// element before it. This is the inverse of `prefix_sum_in_place`.
+///
+/// Overflow behaves like the `-` operator.
+///
+/// ```
+/// use odds::slice::scan::adjacent_difference_in_place;
+///
+/// let mut data = [1, 3, 6, 10, 15];
+/// adjacent_difference_in_place(&mut data);
+/// assert_eq!(data, [1, 2, 3, 4, 5]);
+/// ```
+pub fn adjacent_difference_in_place<T: Num>(data: &mut [T]) {
+    difference(data, Num::sub)
+}
+
+/// Replace each element, except the first, by its difference from the
+/// element before it, wrapping around on overflow.
+pub fn wrapping_adjacent_difference_in_place<T: Int>(data: &mut [T]) {
+    difference(data, Int::wrapping_sub)
+}
+
+/// Replace each element, except the first, by its difference from the
+/// element before it, saturating at the numeric bounds.
+pub fn saturating_adjacent_difference_in_place<T: Int>(data: &mut [T]) {
+    difference(data, Int::saturating_sub)
+}
+
+#[test]
+fn test_prefix_sum() {
+    for len in 0..20 {
+        let data = (0..len as i32).map(|x| x * x - 7).collect::<Vec<_>>();
+
+        let mut sums = data.clone();
+        prefix_sum_in_place(&mut sums);
+        let mut acc = 0;
+        for (i, &x) in data.iter().enumerate() {
+            acc += x;
+            assert_eq!(sums[i], acc);
+        }
+        adjacent_difference_in_place(&mut sums);
+        assert_eq!(sums, data);
+
+        let mut sums = data.clone();
+        suffix_sum_in_place(&mut sums);
+        let mut acc = 0;
+        for (i, &x) in data.iter().enumerate().rev() {
+            acc += x;
+            assert_eq!(sums[i], acc);
+        }
+    }
+}
+
+#[test]
+fn test_prefix_sum_overflow() {
+    let mut data = [200u8; 7];
+    wrapping_prefix_sum_in_place(&mut data);
+    assert_eq!(data, [200, 144, 88, 32, 232, 176, 120]);
+    wrapping_adjacent_difference_in_place(&mut data);
+    assert_eq!(data, [200; 7]);
+
+    let mut data = [200u8; 7];
+    saturating_suffix_sum_in_place(&mut data);
+    assert_eq!(data, [255, 255, 255, 255, 255, 255, 200]);
+
+    let mut data = [10u8, 5, 20];
+    saturating_adjacent_difference_in_place(&mut data);
+    assert_eq!(data, [10, 0, 15]);
+    wrapping_suffix_sum_in_place(&mut data);
+    assert_eq!(data, [25, 15, 15]);
+
+    let mut data = [1.5, 2., 0.5];
+    prefix_sum_in_place(&mut data);
+    assert_eq!(data, [1.5, 3.5, 4.]);
+}
+
+#[test]
+fn test_scan_in_place() {
+    let data = [5u32, 3, 8, 1, 9, 2, 7];
+    let mut v = data;
+    scan_in_place(&mut v, |a, b| a.min(b));
+    assert_eq!(v, [5, 3, 3, 1, 1, 1, 1]);
+    let mut v = data;
+    scan_in_place_rev(&mut v, |a, b| a.max(b));
+    assert_eq!(v, [9, 9, 9, 9, 9, 7, 7]);
+    let mut v = data;
+    scan_in_place(&mut v, |a, b| a * 10 + b);
+    assert_eq!(v[6], 5381927);
+    let mut empty: [u32; 0] = [];
+    scan_in_place(&mut empty, |_, _| unreachable!());
+}
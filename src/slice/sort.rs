@@ -0,0 +1,310 @@
+//! Sorting without allocation
+//!
+//! The sorts in libstd may allocate a buffer; these never do, so they can
+//! be used without `std` and in contexts where allocation is not allowed.
+//!
+//! The `_by` variants take a `less_than` function that returns `true` if
+//! its first argument is ordered before its second.
+
+use super::heap::{make_heap_by, pop_heap_by};
+use super::{rotate_left, SliceExt};
+
+use std::cmp::Ordering;
+
+/// Slices of at most this length are insertion sorted by
+/// `sort_unstable_noalloc`.
+const INSERTION_LIMIT: usize = 20;
+
+/// Sort `data` with insertion sort.
+///
+/// The sort is stable. It takes quadratic time, which is fast for short or
+/// almost sorted slices.
+///
+/// ```
+/// use odds::slice::sort::sort_insertion;
+///
+/// let mut data = [3, 1, 2];
+/// sort_insertion(&mut data);
+/// assert_eq!(data, [1, 2, 3]);
+/// ```
+pub fn sort_insertion<T>(data: &mut [T])
+    where T: Ord
+{
+    sort_insertion_by(data, |a, b| a < b)
+}
+
+/// Sort `data` with insertion sort, in the order given by `less_than`.
+///
+/// The sort is stable.
+pub fn sort_insertion_by<T, F>(data: &mut [T], mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    for i in 1..data.len() {
+        let mut j = i;
+        while j > 0 && less_than(&data[j], &data[j - 1]) {
+            data.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Sort `data` with insertion sort, by the key returned by `key`.
+///
+/// The sort is stable.
+///
+/// ```
+/// use odds::slice::sort::sort_insertion_by_key;
+///
+/// let mut words = ["ccc", "a", "bb", "d"];
+/// sort_insertion_by_key(&mut words, |w| w.len());
+/// assert_eq!(words, ["a", "d", "bb", "ccc"]);
+/// ```
+pub fn sort_insertion_by_key<T, K, F>(data: &mut [T], mut key: F)
+    where K: Ord,
+          F: FnMut(&T) -> K
+{
+    sort_insertion_by(data, |a, b| key(a) < key(b))
+}
+
+/// Sort `data` without allocating, in `O(n log n)` worst case time.
+///
+/// The sort is not stable. Short slices are insertion sorted, and longer
+/// ones are heapsorted.
+///
+/// ```
+/// use odds::slice::sort::sort_unstable_noalloc;
+///
+/// let mut data = [5, 9, 1, 4, 4, 0, 8, 2, 7, 3, 6];
+/// sort_unstable_noalloc(&mut data);
+/// assert_eq!(data, [0, 1, 2, 3, 4, 4, 5, 6, 7, 8, 9]);
+/// ```
+pub fn sort_unstable_noalloc<T>(data: &mut [T])
+    where T: Ord
+{
+    sort_unstable_noalloc_by(data, |a, b| a < b)
+}
+
+/// Sort `data` without allocating, in the order given by `less_than`.
+///
+/// The sort is not stable.
+pub fn sort_unstable_noalloc_by<T, F>(data: &mut [T], mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    if data.len() <= INSERTION_LIMIT {
+        sort_insertion_by(data, less_than);
+        return;
+    }
+    // build a max-heap, then repeatedly move the maximum to the end
+    make_heap_by(data, &mut less_than);
+    for end in (1..data.len()).rev() {
+        pop_heap_by(&mut data[..end + 1], &mut less_than);
+    }
+}
+
+/// Sort `data` without allocating, by the key returned by `key`.
+///
+/// The sort is not stable. The key function is called several times for
+/// each element.
+pub fn sort_unstable_noalloc_by_key<T, K, F>(data: &mut [T], mut key: F)
+    where K: Ord,
+          F: FnMut(&T) -> K
+{
+    sort_unstable_noalloc_by(data, |a, b| key(a) < key(b))
+}
+
+/// Merge the sorted runs `data[..mid]` and `data[mid..]`, so that all of
+/// `data` is sorted.
+///
+/// The merge is stable and does not allocate. It rotates parts of the
+/// slice, using `O(n log n)` moves; use `inplace_merge_buffered` to
+/// merge in linear time when scratch space is available.
+///
+/// **Panics** if `mid` is greater than the length.
+///
+/// ```
+/// use odds::slice::sort::inplace_merge;
+///
+/// let mut data = [1, 4, 7, 2, 3, 8];
+/// inplace_merge(&mut data, 3);
+/// assert_eq!(data, [1, 2, 3, 4, 7, 8]);
+/// ```
+pub fn inplace_merge<T>(data: &mut [T], mid: usize)
+    where T: Ord
+{
+    inplace_merge_by(data, mid, |a, b| a < b)
+}
+
+/// Merge the sorted runs `data[..mid]` and `data[mid..]`, in the order
+/// given by `less_than`.
+///
+/// **Panics** if `mid` is greater than the length.
+pub fn inplace_merge_by<T, F>(data: &mut [T], mid: usize, mut less_than: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    assert!(mid <= data.len(), "inplace_merge: mid out of bounds");
+    merge_rotate(data, mid, &mut less_than);
+}
+
+/// Merge the sorted runs `data[..mid]` and `data[mid..]`, using `buf` as
+/// scratch space.
+///
+/// If `buf` is at least as long as the shorter run, the merge takes linear
+/// time; otherwise it falls back to `inplace_merge`. The merge is stable.
+///
+/// **Panics** if `mid` is greater than the length.
+///
+/// ```
+/// use odds::slice::sort::inplace_merge_buffered;
+///
+/// let mut data = [0, 5, 6, 7, 8, 1, 2];
+/// let mut buf = [0; 2];
+/// inplace_merge_buffered(&mut data, 5, &mut buf);
+/// assert_eq!(data, [0, 1, 2, 5, 6, 7, 8]);
+/// ```
+pub fn inplace_merge_buffered<T>(data: &mut [T], mid: usize, buf: &mut [T])
+    where T: Ord + Copy
+{
+    inplace_merge_buffered_by(data, mid, buf, |a, b| a < b)
+}
+
+/// Merge the sorted runs `data[..mid]` and `data[mid..]`, using `buf` as
+/// scratch space, in the order given by `less_than`.
+///
+/// **Panics** if `mid` is greater than the length.
+pub fn inplace_merge_buffered_by<T, F>(data: &mut [T], mid: usize, buf: &mut [T],
+                                       mut less_than: F)
+    where T: Copy,
+          F: FnMut(&T, &T) -> bool
+{
+    assert!(mid <= data.len(), "inplace_merge: mid out of bounds");
+    let len = data.len();
+    if mid <= buf.len() && mid <= len - mid {
+        // move the left run out, and merge front to back
+        let left = &mut buf[..mid];
+        left.copy_from_slice(&data[..mid]);
+        let (mut i, mut j, mut k) = (0, mid, 0);
+        while i < mid && j < len {
+            if less_than(&data[j], &left[i]) {
+                data[k] = data[j];
+                j += 1;
+            } else {
+                data[k] = left[i];
+                i += 1;
+            }
+            k += 1;
+        }
+        data[k..k + mid - i].copy_from_slice(&left[i..]);
+    } else if len - mid <= buf.len() {
+        // move the right run out, and merge back to front
+        let right = &mut buf[..len - mid];
+        right.copy_from_slice(&data[mid..]);
+        let (mut i, mut j, mut k) = (mid, right.len(), len);
+        while i > 0 && j > 0 {
+            k -= 1;
+            if less_than(&right[j - 1], &data[i - 1]) {
+                data[k] = data[i - 1];
+                i -= 1;
+            } else {
+                data[k] = right[j - 1];
+                j -= 1;
+            }
+        }
+        data[..j].copy_from_slice(&right[..j]);
+    } else {
+        merge_rotate(data, mid, &mut less_than);
+    }
+}
+
+/// Merge without a buffer: split the longer run in half, find where its
+/// middle element goes in the other run, rotate, and merge both sides.
+fn merge_rotate<T, F>(data: &mut [T], mid: usize, less_than: &mut F)
+    where F: FnMut(&T, &T) -> bool
+{
+    let len = data.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    if len == 2 {
+        if less_than(&data[1], &data[0]) {
+            data.swap(0, 1);
+        }
+        return;
+    }
+    let (cut1, cut2);
+    if mid >= len - mid {
+        cut1 = mid / 2;
+        // right elements strictly less than data[cut1] go before it
+        let pivot = &data[cut1];
+        cut2 = mid + data[mid..].lower_bound_by(|x| {
+            if less_than(x, pivot) { Ordering::Less } else { Ordering::Greater }
+        });
+    } else {
+        cut2 = mid + (len - mid) / 2;
+        // left elements not greater than data[cut2] stay before it
+        let pivot = &data[cut2];
+        cut1 = data[..mid].upper_bound_by(|x| {
+            if less_than(pivot, x) { Ordering::Greater } else { Ordering::Less }
+        });
+    }
+    rotate_left(&mut data[cut1..cut2], mid - cut1);
+    let new_mid = cut1 + (cut2 - mid);
+    let (left, right) = data.split_at_mut(new_mid);
+    merge_rotate(left, cut1, less_than);
+    merge_rotate(right, cut2 - new_mid, less_than);
+}
+
+#[test]
+fn test_sorts() {
+    use random::{RandomSource, XorShift};
+
+    let mut rng = XorShift::new(9);
+    for len in (0..40).chain(Some(1000)) {
+        let data = (0..len).map(|i| (rng.below(8), i)).collect::<Vec<_>>();
+        let mut stable = data.clone();
+        stable.sort_by_key(|x| x.0);
+
+        let mut v = data.clone();
+        sort_insertion_by_key(&mut v, |x| x.0);
+        assert_eq!(v, stable);
+
+        let mut v = data.clone();
+        sort_unstable_noalloc_by_key(&mut v, |x| x.0);
+        assert!(v.windows(2).all(|w| w[0].0 <= w[1].0));
+        v.sort();
+        let mut expected = data.clone();
+        expected.sort();
+        assert_eq!(v, expected);
+
+        let mut v = data.clone();
+        sort_unstable_noalloc_by(&mut v, |a, b| a > b);
+        expected.reverse();
+        assert_eq!(v, expected);
+    }
+}
+
+#[test]
+fn test_inplace_merge() {
+    use random::{RandomSource, XorShift};
+
+    let mut rng = XorShift::new(17);
+    for len in 0..60 {
+        for _ in 0..4 {
+            let mid = rng.below(len + 1);
+            let mut data = (0..len).map(|i| (rng.below(6), i)).collect::<Vec<_>>();
+            data[..mid].sort_by_key(|x| x.0);
+            data[mid..].sort_by_key(|x| x.0);
+            let mut expected = data.clone();
+            expected.sort_by_key(|x| x.0);
+
+            let mut v = data.clone();
+            inplace_merge_by(&mut v, mid, |a, b| a.0 < b.0);
+            assert_eq!(v, expected);
+            for &buf_len in &[0, 3, 30] {
+                let mut buf = vec![(0, 0); buf_len];
+                let mut v = data.clone();
+                inplace_merge_buffered_by(&mut v, mid, &mut buf, |a, b| a.0 < b.0);
+                assert_eq!(v, expected);
+            }
+        }
+    }
+}
@@ -2,10 +2,11 @@
 
 
 use std::mem::size_of;
-use std::mem::uninitialized;
 use std::marker::PhantomData;
 use std::ptr;
+use std::slice::from_raw_parts;
 
+use pointer::PointerExt;
 use slice::Pod;
 use slice::iter::SliceCopyIter;
 
@@ -49,7 +50,7 @@ impl<'a, T> UnalignedIter<'a, T> {
             let len = data.len();
             let sz = size_of::<T>() as isize;
             let end_block = ptr.offset(len as isize / sz * sz);
-            let end = ptr.offset(len as isize);
+            let end = ptr.debug_add(len);
             UnalignedIter {
                 ptr: ptr,
                 end: end_block,
@@ -73,6 +74,23 @@ impl<'a, T> UnalignedIter<'a, T> {
         self.ptr != self.tail_end
     }
 
+    /// Return the trailing bytes that are too few to make up a whole `T`,
+    /// and which the iterator will never yield.
+    ///
+    /// ```
+    /// use odds::slice::unalign::UnalignedIter;
+    ///
+    /// let data = [1u8, 0, 0, 0, 2, 0, 0, 0, 0xff, 0xfe];
+    /// let iter = UnalignedIter::<u32>::from_slice(&data);
+    /// assert_eq!(iter.remainder(), &[0xff, 0xfe]);
+    /// assert_eq!(iter.map(u32::from_le).collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    pub fn remainder(&self) -> &'a [u8] {
+        unsafe {
+            from_raw_parts(self.end, self.tail_end as usize - self.end as usize)
+        }
+    }
+
     /// Return the next iterator element, without stepping the iterator.
     pub fn peek_next(&self) -> Option<T> where T: Copy {
         if self.ptr != self.end {
@@ -86,9 +104,7 @@ impl<'a, T> UnalignedIter<'a, T> {
 }
 
 unsafe fn load_unaligned<T>(p: *const u8) -> T where T: Copy {
-    let mut x = uninitialized();
-    ptr::copy_nonoverlapping(p, &mut x as *mut _ as *mut u8, size_of::<T>());
-    x
+    ptr::read_unaligned(p as *const T)
 }
 
 impl<'a, T> Iterator for UnalignedIter<'a, T>
@@ -99,15 +115,22 @@ impl<'a, T> Iterator for UnalignedIter<'a, T>
         if self.ptr != self.end {
             unsafe {
                 let elt = Some(load_unaligned::<T>(self.ptr));
-                self.ptr = self.ptr.offset(size_of::<T>() as isize);
+                self.ptr = self.ptr.debug_add(size_of::<T>());
                 elt
             }
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end as usize - self.ptr as usize) / size_of::<T>();
+        (len, Some(len))
+    }
 }
 
+impl<'a, T> ExactSizeIterator for UnalignedIter<'a, T> where T: Copy { }
+
 
 #[test]
 fn test_unalign() {
@@ -115,6 +138,8 @@ fn test_unalign() {
     let mut iter = UnalignedIter::<u32>::from_slice(&data);
     assert_eq!(iter.next(), Some(u32::from_be(0x00010203)));
     assert_eq!(iter.next(), Some(u32::from_be(0x04050607)));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.remainder(), &[8, 9]);
     let mut tail = iter.tail();
     assert_eq!(tail.next(), Some(8));
     assert_eq!(tail.next(), Some(9));
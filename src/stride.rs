@@ -10,6 +10,7 @@
 //! option. This file may not be copied, modified, or distributed
 //! except according to those terms.
 
+use std::cmp;
 use std::fmt;
 use std::marker;
 use std::mem;
@@ -301,3 +302,97 @@ impl<'a, A> IndexMut<usize> for StrideMut<'a, A> {
         }
     }
 }
+
+/// Copy `n` elements from `src` to `dst`, stepping the pointers by
+/// `sstride` and `dstride` elements respectively.
+#[inline]
+unsafe fn copy_strided_raw<A: Copy>(src: *const A, sstride: isize,
+                                    dst: *mut A, dstride: isize, n: usize)
+{
+    let n = n as isize;
+    let mut i = 0;
+    while i + 4 <= n {
+        *dst.offset(i * dstride) = *src.offset(i * sstride);
+        *dst.offset((i + 1) * dstride) = *src.offset((i + 1) * sstride);
+        *dst.offset((i + 2) * dstride) = *src.offset((i + 2) * sstride);
+        *dst.offset((i + 3) * dstride) = *src.offset((i + 3) * sstride);
+        i += 4;
+    }
+    while i < n {
+        *dst.offset(i * dstride) = *src.offset(i * sstride);
+        i += 1;
+    }
+}
+
+/// Copy the elements of the strided view `src` into the contiguous `dst`.
+///
+/// Copies as many elements as the shorter of the two has, and returns
+/// that number.
+///
+/// ```
+/// use odds::stride::{Stride, gather};
+///
+/// // deinterleave the left channel of stereo samples
+/// let samples = [1, -1, 2, -2, 3, -3];
+/// let mut left = [0; 3];
+/// assert_eq!(gather(Stride::from_slice(&samples, 2), &mut left), 3);
+/// assert_eq!(left, [1, 2, 3]);
+/// ```
+pub fn gather<A: Copy>(src: Stride<A>, dst: &mut [A]) -> usize {
+    let n = cmp::min(src.len(), dst.len());
+    unsafe {
+        // an exhausted stride's offset may point past the end, so it must
+        // not be used with `offset`; it's only dereferenced if n > 0
+        copy_strided_raw(src.begin.wrapping_offset(src.offset), src.stride,
+                         dst.as_mut_ptr(), 1, n);
+    }
+    n
+}
+
+/// Copy the elements of the contiguous `src` into the strided view `dst`.
+///
+/// Copies as many elements as the shorter of the two has, and returns
+/// that number.
+///
+/// ```
+/// use odds::stride::{StrideMut, scatter};
+///
+/// // interleave the right channel into stereo samples
+/// let mut samples = [1, 0, 2, 0, 3, 0];
+/// let right = [-1, -2, -3];
+/// assert_eq!(scatter(&right, StrideMut::from_slice(&mut samples[1..], 2)), 3);
+/// assert_eq!(samples, [1, -1, 2, -2, 3, -3]);
+/// ```
+pub fn scatter<A: Copy>(src: &[A], dst: StrideMut<A>) -> usize {
+    let n = cmp::min(src.len(), dst.len());
+    unsafe {
+        copy_strided_raw(src.as_ptr(), 1,
+                         dst.begin.wrapping_offset(dst.offset), dst.stride, n);
+    }
+    n
+}
+
+/// Copy the elements of the strided view `src` into the strided view `dst`.
+///
+/// Copies as many elements as the shorter of the two has, and returns
+/// that number.
+///
+/// ```
+/// use odds::stride::{Stride, StrideMut, copy_strided};
+///
+/// // repack RGB pixels into RGBA pixels, red channel only
+/// let rgb = [10, 11, 12, 20, 21, 22];
+/// let mut rgba = [0; 8];
+/// let n = copy_strided(Stride::from_slice(&rgb, 3),
+///                      StrideMut::from_slice(&mut rgba, 4));
+/// assert_eq!(n, 2);
+/// assert_eq!(rgba, [10, 0, 0, 0, 20, 0, 0, 0]);
+/// ```
+pub fn copy_strided<A: Copy>(src: Stride<A>, dst: StrideMut<A>) -> usize {
+    let n = cmp::min(src.len(), dst.len());
+    unsafe {
+        copy_strided_raw(src.begin.wrapping_offset(src.offset), src.stride,
+                         dst.begin.wrapping_offset(dst.offset), dst.stride, n);
+    }
+    n
+}
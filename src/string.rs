@@ -1,5 +1,11 @@
 //! Extensions to `&str` and `String`
 //!
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+#[cfg(feature="std")]
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter;
 #[cfg(feature="std")]
 use std::ptr;
@@ -7,6 +13,10 @@ use std::str;
 use std::ops::Deref;
 
 use IndexRange;
+use pointer::PointerExt;
+use slice;
+use slice::finder::Finder;
+use slice::lines::{ByteLines, LineOptions};
 
 /// Extra methods for `str`
 pub trait StrExt {
@@ -47,6 +57,108 @@ pub trait StrExt {
     /// }
     /// ```
     fn is_acceptable_index(&self, index: usize) -> bool;
+
+    /// Return the longest prefix of the string that is at most `n` bytes
+    /// long, without splitting a character.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// // 'é' is two bytes, so it doesn't fit in four
+    /// assert_eq!("café".prefix(4), "caf");
+    /// assert_eq!("café".prefix(5), "café");
+    /// assert_eq!("café".suffix(2), "é");
+    /// assert_eq!("café".suffix(1), "");
+    /// ```
+    fn prefix(&self, n: usize) -> &str;
+
+    /// Return the longest suffix of the string that is at most `n` bytes
+    /// long, without splitting a character.
+    fn suffix(&self, n: usize) -> &str;
+
+    /// Split the string into its first character and the rest, or return
+    /// `None` if it is empty.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// assert_eq!("λx".slice_shift_char(), Some(('λ', "x")));
+    /// assert_eq!("λx".slice_pop_char(), Some(("λ", 'x')));
+    /// assert_eq!("".slice_shift_char(), None);
+    /// ```
+    fn slice_shift_char(&self) -> Option<(char, &str)>;
+
+    /// Split the string into the start and its last character, or return
+    /// `None` if it is empty.
+    fn slice_pop_char(&self) -> Option<(&str, char)>;
+
+    /// Return the greatest acceptable index that is at most `index`.
+    ///
+    /// Indices past the end are clamped to `self.len()`.
+    ///
+    /// Rust 1.91 and later have an inherent `str` method of the same name;
+    /// use `StrExt::floor_char_boundary(s, index)` to call this one.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// let s = "aβc";
+    /// // index 2 is inside the 'β'
+    /// assert_eq!(StrExt::floor_char_boundary(s, 2), 1);
+    /// assert_eq!(StrExt::ceil_char_boundary(s, 2), 3);
+    /// assert_eq!(StrExt::floor_char_boundary(s, 10), 4);
+    /// ```
+    fn floor_char_boundary(&self, index: usize) -> usize;
+
+    /// Return the smallest acceptable index that is at least `index`.
+    ///
+    /// Indices past the end are clamped to `self.len()`.
+    ///
+    /// Rust 1.91 and later have an inherent `str` method of the same name;
+    /// use `StrExt::ceil_char_boundary(s, index)` to call this one.
+    fn ceil_char_boundary(&self, index: usize) -> usize;
+
+    /// Split the string by whitespace, like `split_whitespace`, and yield
+    /// each word together with its byte offset in the string.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// let words = "  key =\tvalue ".split_words_with_indices().collect::<Vec<_>>();
+    /// assert_eq!(words, [(2, "key"), (6, "="), (8, "value")]);
+    /// ```
+    fn split_words_with_indices<'a>(&'a self) -> WordIndices<'a>;
+
+    /// Split the string into lines, keeping the `\n` or `\r\n` terminator
+    /// at the end of each line.
+    ///
+    /// Unlike `lines`, the lines concatenate to the whole string again.
+    /// The last line has no terminator if the string doesn't end with
+    /// `\n`; see `LinesWithTerminator::is_unterminated`.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// let text = "one\r\ntwo\n\nthree";
+    /// let lines = text.lines_with_terminator();
+    /// assert!(lines.is_unterminated());
+    /// assert_eq!(lines.collect::<Vec<_>>(), ["one\r\n", "two\n", "\n", "three"]);
+    /// ```
+    fn lines_with_terminator<'a>(&'a self) -> LinesWithTerminator<'a>;
+
+    /// Split the string at each occurrence of any of the chars in `delims`.
+    ///
+    /// Like `split` with a `&[char]` pattern, this yields every piece,
+    /// including empty ones. When all delimiters are ASCII, the search
+    /// uses a lookup table and checks one byte at a time.
+    ///
+    /// ```
+    /// use odds::string::StrExt;
+    ///
+    /// let fields = "a,b;;c|d".split_any(&[',', ';', '|']).collect::<Vec<_>>();
+    /// assert_eq!(fields, ["a", "b", "", "c", "d"]);
+    /// ```
+    fn split_any<'a, 'd>(&'a self, delims: &'d [char]) -> SplitAny<'a, 'd>;
 }
 
 /// Extension trait for `str` for string slicing without panicking
@@ -91,6 +203,73 @@ impl StrExt for str {
             })
         }
     }
+
+    fn prefix(&self, n: usize) -> &str {
+        &self[..StrExt::floor_char_boundary(self, n)]
+    }
+
+    fn suffix(&self, n: usize) -> &str {
+        if n >= self.len() {
+            return self;
+        }
+        &self[StrExt::ceil_char_boundary(self, self.len() - n)..]
+    }
+
+    fn slice_shift_char(&self) -> Option<(char, &str)> {
+        self.chars().next().map(|c| (c, &self[c.len_utf8()..]))
+    }
+
+    fn slice_pop_char(&self) -> Option<(&str, char)> {
+        self.chars().next_back().map(|c| (&self[..self.len() - c.len_utf8()], c))
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
+        }
+        // at most three continuation bytes precede a boundary
+        let mut i = index;
+        while !self.is_acceptable_index(i) {
+            i -= 1;
+        }
+        i
+    }
+
+    fn ceil_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
+        }
+        let mut i = index;
+        while !self.is_acceptable_index(i) {
+            i += 1;
+        }
+        i
+    }
+
+    fn split_words_with_indices<'a>(&'a self) -> WordIndices<'a> {
+        WordIndices { s: self, start: 0, end: self.len() }
+    }
+
+    fn lines_with_terminator<'a>(&'a self) -> LinesWithTerminator<'a> {
+        let options = LineOptions::new().keep_terminator(true);
+        LinesWithTerminator {
+            iter: ByteLines::with_options(self.as_bytes(), options),
+            unterminated: !self.is_empty() && !self.ends_with('\n'),
+        }
+    }
+
+    fn split_any<'a, 'd>(&'a self, delims: &'d [char]) -> SplitAny<'a, 'd> {
+        let delims = if delims.iter().all(|c| c.is_ascii()) {
+            let mut table = [0u64; 4];
+            for &c in delims {
+                table[c as usize / 64] |= 1 << (c as usize % 64);
+            }
+            Delimiters::Ascii(table)
+        } else {
+            Delimiters::Chars(delims)
+        };
+        SplitAny { s: self, start: 0, finished: false, delims: delims }
+    }
 }
 
 impl StrSlice for str {
@@ -105,6 +284,79 @@ impl StrSlice for str {
     }
 }
 
+/// Return the byte index of the first occurrence of `needle` in
+/// `haystack`, or `None`.
+///
+/// This uses the two-way algorithm; to search for the same needle many
+/// times, create a [`Finder`](../slice/finder/struct.Finder.html) once
+/// instead.
+///
+/// ```
+/// use odds::string::find_str;
+///
+/// assert_eq!(find_str("one two three", "two"), Some(4));
+/// assert_eq!(find_str("one two three", "four"), None);
+/// ```
+pub fn find_str(haystack: &str, needle: &str) -> Option<usize> {
+    Finder::new(needle.as_bytes()).find_str(haystack)
+}
+
+/// Convert the ASCII letters in `s` to lowercase, in place.
+///
+/// Like `str::make_ascii_lowercase`, but converts eight bytes at a time.
+/// See [`slice::make_ascii_lowercase_fast`](../slice/fn.make_ascii_lowercase_fast.html).
+///
+/// ```
+/// use odds::string::make_ascii_lowercase_fast;
+///
+/// let mut s = String::from("ÀCCEPT-Language");
+/// make_ascii_lowercase_fast(&mut s);
+/// assert_eq!(s, "Àccept-language");
+/// ```
+pub fn make_ascii_lowercase_fast(s: &mut str) {
+    // changing ASCII bytes to ASCII bytes keeps the string valid
+    unsafe {
+        slice::make_ascii_lowercase_fast(s.as_bytes_mut())
+    }
+}
+
+/// Convert the ASCII letters in `s` to uppercase, in place.
+///
+/// Like `str::make_ascii_uppercase`, but converts eight bytes at a time.
+pub fn make_ascii_uppercase_fast(s: &mut str) {
+    unsafe {
+        slice::make_ascii_uppercase_fast(s.as_bytes_mut())
+    }
+}
+
+/// Return the longest common prefix of `a` and `b` that does not split a
+/// char.
+///
+/// The strings are compared a word at a time.
+///
+/// ```
+/// use odds::string::{common_prefix, common_suffix};
+///
+/// assert_eq!(common_prefix("interstellar", "internet"), "inter");
+/// // 'é' and 'è' share their first byte
+/// assert_eq!(common_prefix("café", "cafè"), "caf");
+/// assert_eq!(common_suffix("running", "jumping"), "ing");
+/// ```
+pub fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let n = slice::shared_prefix(a.as_bytes(), b.as_bytes());
+    // the prefix is the same in both, so a boundary in `a` is one in `b`
+    &a[..StrExt::floor_char_boundary(a, n)]
+}
+
+/// Return the longest common suffix of `a` and `b` that does not split a
+/// char.
+///
+/// The strings are compared a word at a time.
+pub fn common_suffix<'a>(a: &'a str, b: &str) -> &'a str {
+    let n = slice::shared_suffix(a.as_bytes(), b.as_bytes());
+    &a[StrExt::ceil_char_boundary(a, a.len() - n)..]
+}
+
 /// Iterator of all non-empty prefixes
 #[derive(Clone)]
 pub struct Prefixes<'a> {
@@ -149,6 +401,136 @@ impl<'a> Iterator for Substrings<'a> {
     }
 }
 
+/// Iterator of the whitespace separated words of a string, with their byte
+/// offsets.
+///
+/// See [`.split_words_with_indices()`](trait.StrExt.html#tymethod.split_words_with_indices)
+/// for more information.
+#[derive(Clone, Debug)]
+pub struct WordIndices<'a> {
+    s: &'a str,
+    /// The words not yet yielded are in `s[start..end]`
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for WordIndices<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let rest = &self.s[self.start..self.end];
+        let word_start = match rest.find(|c: char| !c.is_whitespace()) {
+            Some(i) => self.start + i,
+            None => {
+                self.start = self.end;
+                return None;
+            }
+        };
+        let word_end = self.s[word_start..self.end].find(char::is_whitespace)
+                                                   .map_or(self.end, |i| word_start + i);
+        self.start = word_end;
+        Some((word_start, &self.s[word_start..word_end]))
+    }
+}
+
+impl<'a> DoubleEndedIterator for WordIndices<'a> {
+    fn next_back(&mut self) -> Option<(usize, &'a str)> {
+        let rest = &self.s[self.start..self.end];
+        let word_end = match rest.rfind(|c: char| !c.is_whitespace()) {
+            Some(i) => {
+                let c = char_get(rest, i).unwrap();
+                self.start + i + c.len_utf8()
+            }
+            None => {
+                self.end = self.start;
+                return None;
+            }
+        };
+        let word_start = self.s[self.start..word_end].rfind(char::is_whitespace)
+                                                     .map_or(self.start, |i| {
+            self.start + i + char_get(&self.s[self.start..], i).unwrap().len_utf8()
+        });
+        self.end = word_start;
+        Some((word_start, &self.s[word_start..word_end]))
+    }
+}
+
+/// Iterator of the lines of a string, with their terminators.
+///
+/// See [`.lines_with_terminator()`](trait.StrExt.html#tymethod.lines_with_terminator)
+/// for more information.
+#[derive(Clone, Debug)]
+pub struct LinesWithTerminator<'a> {
+    iter: ByteLines<'a>,
+    unterminated: bool,
+}
+
+impl<'a> LinesWithTerminator<'a> {
+    /// Return `true` if the string ends with a line that has no `\n`
+    /// terminator.
+    pub fn is_unterminated(&self) -> bool {
+        self.unterminated
+    }
+}
+
+impl<'a> Iterator for LinesWithTerminator<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // lines are split after a `\n`, which is always a char boundary
+        self.iter.next().map(|line| unsafe { str::from_utf8_unchecked(line) })
+    }
+}
+
+/// Iterator of the parts of a string separated by any of a set of chars.
+///
+/// See [`.split_any()`](trait.StrExt.html#tymethod.split_any) for more
+/// information.
+#[derive(Clone, Debug)]
+pub struct SplitAny<'a, 'd> {
+    s: &'a str,
+    start: usize,
+    finished: bool,
+    delims: Delimiters<'d>,
+}
+
+#[derive(Clone, Debug)]
+enum Delimiters<'d> {
+    /// Bit `b` is set for each delimiter byte `b`
+    Ascii([u64; 4]),
+    Chars(&'d [char]),
+}
+
+impl<'a, 'd> Iterator for SplitAny<'a, 'd> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.finished {
+            return None;
+        }
+        let rest = &self.s[self.start..];
+        let found = match self.delims {
+            // ASCII bytes never occur inside a multibyte char
+            Delimiters::Ascii(ref table) => rest.bytes().position(|b| {
+                (table[b as usize / 64] >> (b % 64)) & 1 != 0
+            }).map(|i| (i, 1)),
+            Delimiters::Chars(delims) => rest.char_indices()
+                                             .find(|&(_, c)| delims.contains(&c))
+                                             .map(|(i, c)| (i, c.len_utf8())),
+        };
+        match found {
+            Some((i, width)) => {
+                self.start += i + width;
+                Some(&rest[..i])
+            }
+            None => {
+                self.finished = true;
+                Some(rest)
+            }
+        }
+    }
+}
+
 #[cfg(feature="std")]
 /// Extra methods for `String`
 ///
@@ -168,11 +550,11 @@ impl StringExt for String {
         unsafe {
             let v = self.as_mut_vec();
             let ptr = v.as_mut_ptr();
-            ptr::copy(ptr.offset(index as isize),
-                      ptr.offset((index + s.len()) as isize),
+            ptr::copy(ptr.debug_add(index),
+                      ptr.debug_add(index + s.len()),
                       v.len() - index);
             ptr::copy_nonoverlapping(s.as_ptr(),
-                                     ptr.offset(index as isize),
+                                     ptr.debug_add(index),
                                      s.len());
             let new_len = v.len() + s.len();
             v.set_len(new_len);
@@ -185,12 +567,29 @@ pub trait StrChunksWindows {
     /// Return an iterator that splits the string in substrings of each `n`
     /// `char` per substring. The last item will contain the remainder if
     /// `n` does not divide the char length of the string evenly.
+    ///
+    /// ```
+    /// use odds::string::StrChunksWindows;
+    ///
+    /// let chunks = "αβγδε".char_chunks(2).collect::<Vec<_>>();
+    /// assert_eq!(chunks, ["αβ", "γδ", "ε"]);
+    /// ```
     fn char_chunks(&self, n: usize) -> CharChunks;
 
     /// Return an iterator that produces substrings of each `n`
     /// `char` per substring in a sliding window that advances one char at a time.
     ///
+    /// If the string has less than `n` chars, the iterator is empty.
+    ///
     /// ***Panics*** if `n` is zero.
+    ///
+    /// ```
+    /// use odds::string::StrChunksWindows;
+    ///
+    /// // character trigrams for a search index
+    /// let trigrams = "café".char_windows(3).collect::<Vec<_>>();
+    /// assert_eq!(trigrams, ["caf", "afé"]);
+    /// ```
     fn char_windows(&self, n: usize) -> CharWindows;
 }
 
@@ -284,6 +683,17 @@ impl<'a> Iterator for CharWindows<'a> {
 }
 
 /// A single-char string.
+///
+/// This encodes a `char` inline, so it can be passed as a `&str` without
+/// allocating.
+///
+/// ```
+/// use odds::string::CharStr;
+///
+/// let sep = CharStr::from('→');
+/// assert_eq!(["a", "b"].join(&sep), "a→b");
+/// assert_eq!(sep.len(), 3);
+/// ```
 #[derive(Copy, Clone, Debug)]
 pub struct CharStr {
     buf: [u8; 4],
@@ -311,10 +721,280 @@ impl Deref for CharStr {
     }
 }
 
+impl From<char> for CharStr {
+    fn from(c: char) -> CharStr {
+        CharStr::new(c)
+    }
+}
+
+impl AsRef<str> for CharStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for CharStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self)
+    }
+}
+
+/// Error returned when a string does not fit in the remaining capacity of
+/// an `InlineStr`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CapacityError(());
+
+impl fmt::Display for CapacityError {
+    #[inline]
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.pad(self.description())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CapacityError {
+    #[inline]
+    fn description(&self) -> &str {
+        CapacityError::description(self)
+    }
+}
+
+impl CapacityError {
+    #[inline]
+    pub fn description(&self) -> &str {
+        "insufficient capacity in the inline string"
+    }
+}
+
+/// A string stored inline, with a capacity of `N` bytes.
+///
+/// It never allocates, so it is usable without `std`. Appending fails with
+/// `CapacityError` instead of truncating, and leaves the string unchanged.
+///
+/// ```
+/// use std::fmt::Write;
+/// use odds::string::InlineStr;
+///
+/// let mut key = InlineStr::<8>::new();
+/// key.push_str("id-").unwrap();
+/// write!(key, "{}", 42).unwrap();
+/// assert_eq!(&key[..], "id-42");
+///
+/// // "ééé" is six bytes, and only three are left
+/// assert!(key.push_str("ééé").is_err());
+/// assert_eq!(key, "id-42");
+/// ```
+#[derive(Copy, Clone)]
+pub struct InlineStr<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> InlineStr<N> {
+    /// Create a new empty string.
+    pub fn new() -> Self {
+        InlineStr {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Return the capacity in bytes, `N`.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Return the number of bytes that can still be appended.
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Return the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Append `c` to the end of the string.
+    ///
+    /// Return an error if it does not fit.
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        match ::char::encode_utf8(c, &mut self.buf[self.len..]) {
+            Ok(n) => {
+                self.len += n;
+                Ok(())
+            }
+            Err(_) => Err(CapacityError(())),
+        }
+    }
+
+    /// Append `s` to the end of the string.
+    ///
+    /// Return an error if it does not fit; nothing is appended then.
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError(()));
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+
+    /// Remove the last char and return it, or `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.chars().next_back();
+        if let Some(c) = c {
+            self.len -= c.len_utf8();
+        }
+        c
+    }
+
+    /// Shorten the string to `new_len` bytes.
+    ///
+    /// Does nothing if `new_len` is not less than the length.
+    ///
+    /// **Panics** if `new_len` is not on a char boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len {
+            assert!(self.is_char_boundary(new_len),
+                    "InlineStr::truncate: not a char boundary");
+            self.len = new_len;
+        }
+    }
+
+    /// Remove all of the string.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for InlineStr<N> {
+    fn default() -> Self {
+        InlineStr::new()
+    }
+}
+
+impl<const N: usize> Deref for InlineStr<N> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(&self.buf[..self.len])
+        }
+    }
+}
+
+impl<const N: usize> AsRef<str> for InlineStr<N> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> Borrow<str> for InlineStr<N> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> fmt::Write for InlineStr<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Display for InlineStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self)
+    }
+}
+
+impl<const N: usize> fmt::Debug for InlineStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<const N: usize> PartialEq for InlineStr<N> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<const N: usize> Eq for InlineStr<N> { }
+
+impl<const N: usize> PartialEq<str> for InlineStr<N> {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl<'a, const N: usize> PartialEq<&'a str> for InlineStr<N> {
+    fn eq(&self, other: &&'a str) -> bool {
+        &**self == *other
+    }
+}
+
+impl<const N: usize> PartialOrd for InlineStr<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for InlineStr<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<const N: usize> Hash for InlineStr<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
 #[test]
 fn test_char_str() {
     let s = CharStr::new('α');
     assert_eq!(&s[..], "α");
+    for &c in &['a', 'ß', '€', '𝄞'] {
+        let s = CharStr::from(c);
+        assert_eq!(s.as_ref(), c.to_string());
+        assert_eq!(s.chars().collect::<Vec<_>>(), [c]);
+    }
+    assert_eq!(format!("[{:>3}]", CharStr::from('x')), "[  x]");
+}
+
+#[test]
+fn test_inline_str() {
+    use std::fmt::Write;
+
+    let mut s = InlineStr::<5>::new();
+    assert_eq!(s, "");
+    assert_eq!(s.remaining_capacity(), 5);
+    s.push('a').unwrap();
+    s.push('€').unwrap();
+    assert_eq!(s.len(), 4);
+    assert!(s.push('β').is_err());
+    assert!(s.push_str("bc").is_err());
+    assert_eq!(s, "a€");
+    s.push('b').unwrap();
+    assert!(write!(s, "{}", 1).is_err());
+    assert_eq!(s.pop(), Some('b'));
+    assert_eq!(s.pop(), Some('€'));
+    s.push_str("bcd").unwrap();
+    assert_eq!(s, "abcd");
+    s.truncate(2);
+    assert_eq!(format!("{:?} {:>3}", s, s), "\"ab\"  ab");
+    assert!(s > InlineStr::<5>::default());
+    s.clear();
+    assert_eq!(s.pop(), None);
+    assert_eq!(s, InlineStr::default());
+
+    let mut set = ::std::collections::HashSet::new();
+    set.insert(InlineStr::<4>::new());
+    assert!(set.contains(""));
 }
 
 #[test]
@@ -326,6 +1006,14 @@ fn str_windows() {
     assert_eq!(CharWindows::new("αbγ", 2).nth(1), Some("bγ"));
 }
 
+#[test]
+fn str_chunks() {
+    assert_eq!(CharChunks::new("", 2).next(), None);
+    assert!(CharChunks::new("abcd", 2).eq(vec!["ab", "cd"]));
+    assert!(CharChunks::new("aβc", 5).eq(vec!["aβc"]));
+    assert!(CharChunks::new("aβcδe", 1).eq(vec!["a", "β", "c", "δ", "e"]));
+}
+
 #[test]
 #[should_panic]
 fn str_windows_not_0() {
@@ -353,6 +1041,136 @@ fn test_acc_index() {
     assert!(t.is_acceptable_index(0));
 }
 
+#[test]
+fn test_prefix_suffix() {
+    let s = "aβ€𝄞z";
+    for n in 0..s.len() + 2 {
+        let p = s.prefix(n);
+        assert!(p.len() <= n && s.starts_with(p));
+        let next = s[p.len()..].chars().next().map_or(0, char::len_utf8);
+        assert!(p.len() == s.len() || p.len() + next > n);
+
+        let t = s.suffix(n);
+        assert!(t.len() <= n && s.ends_with(t));
+        let prev = s[..s.len() - t.len()].chars().rev().next().map_or(0, char::len_utf8);
+        assert!(t.len() == s.len() || t.len() + prev > n);
+    }
+}
+
+#[test]
+fn test_char_boundary() {
+    let s = "aβ€𝄞z";
+    for i in 0..s.len() + 3 {
+        let floor = StrExt::floor_char_boundary(s, i);
+        let ceil = StrExt::ceil_char_boundary(s, i);
+        assert!(s.is_acceptable_index(floor) && s.is_acceptable_index(ceil));
+        assert!(floor <= ceil && ceil <= s.len());
+        if i <= s.len() {
+            assert!(floor <= i && i <= ceil);
+            assert!((floor..ceil + 1).filter(|&j| s.is_acceptable_index(j)).count() <=
+                    if floor == ceil { 1 } else { 2 });
+        } else {
+            assert_eq!((floor, ceil), (s.len(), s.len()));
+        }
+    }
+    assert_eq!(StrExt::floor_char_boundary("", 1), 0);
+    assert_eq!(StrExt::ceil_char_boundary("", 0), 0);
+}
+
+#[test]
+fn test_word_indices() {
+    for &s in &["", "   ", "a", " a ", "one two", "\u{3000}αβ\t γ\u{a0}δ\n\nx  ", " a\u{2003}b"] {
+        let words = s.split_words_with_indices().collect::<Vec<_>>();
+        assert!(words.iter().map(|w| w.1).eq(s.split_whitespace()));
+        for &(i, w) in &words {
+            assert_eq!(&s[i..i + w.len()], w);
+        }
+        let mut rev = s.split_words_with_indices().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, words);
+
+        // meet in the middle
+        let mut iter = s.split_words_with_indices();
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        loop {
+            match iter.next() {
+                Some(w) => front.push(w),
+                None => break,
+            }
+            match iter.next_back() {
+                Some(w) => back.push(w),
+                None => break,
+            }
+        }
+        assert_eq!(iter.next(), None);
+        front.extend(back.into_iter().rev());
+        assert_eq!(front, words);
+    }
+}
+
+#[test]
+fn test_lines_with_terminator() {
+    for &s in &["", "\n", "a", "a\n", "\r\n\r\n", "α\rβ\r\n\nγ", "x\n\n\ny\r"] {
+        let lines = s.lines_with_terminator();
+        assert_eq!(lines.is_unterminated(), s.lines().count() > s.matches('\n').count());
+        let lines = lines.collect::<Vec<_>>();
+        assert_eq!(lines.concat(), s);
+        assert!(lines.iter().map(|l| match l.strip_suffix('\n') {
+            Some(l) => l.strip_suffix('\r').unwrap_or(l),
+            None => l,
+        }).eq(s.lines()));
+        for line in &lines[..lines.len().saturating_sub(1)] {
+            assert!(line.ends_with('\n'));
+        }
+    }
+}
+
+#[test]
+fn test_split_any() {
+    let delim_sets: &[&[char]] = &[&[], &[','], &[',', ';', ' '], &['\x7f', '\0'],
+                                   &['β', ','], &['€', '𝄞']];
+    for &s in &["", ",", "a,b", ",a;;b c,", "αβγ,β€;x\x7f\0", "𝄞€𝄞"] {
+        for &delims in delim_sets {
+            assert!(s.split_any(delims).eq(s.split(delims)), "{:?} {:?}", s, delims);
+        }
+    }
+}
+
+#[test]
+fn test_common_prefix_suffix() {
+    let words = ["", "a", "αβγ", "αβδ", "xαβγ", "€", "₤", "€€€€€€€€€€€€€€€€€€€€x",
+                 "€€€€€€€€€€€€€€€€€€€€y", "y₤€€€€€€€€€€€€€€€€€€€"];
+    for &a in &words {
+        for &b in &words {
+            let expected = a.char_indices().map(|(i, _)| i).chain(Some(a.len()))
+                            .filter(|&i| b.starts_with(&a[..i])).last().unwrap();
+            assert_eq!(common_prefix(a, b), &a[..expected]);
+            let expected = a.char_indices().map(|(i, _)| i).chain(Some(a.len()))
+                            .filter(|&i| b.ends_with(&a[i..])).next().unwrap();
+            assert_eq!(common_suffix(a, b), &a[expected..]);
+        }
+    }
+}
+
+#[test]
+fn test_shift_pop_char() {
+    let mut s = "a€𝄞";
+    let mut chars = Vec::new();
+    while let Some((c, rest)) = s.slice_shift_char() {
+        chars.push(c);
+        s = rest;
+    }
+    assert_eq!(chars, ['a', '€', '𝄞']);
+    let mut s = "a€𝄞";
+    while let Some((rest, c)) = s.slice_pop_char() {
+        assert_eq!(chars.pop(), Some(c));
+        s = rest;
+    }
+    assert!(chars.is_empty());
+    assert_eq!("".slice_pop_char(), None);
+}
+
 #[test]
 fn test_string_ext() {
     let mut s = String::new();
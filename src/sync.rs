@@ -0,0 +1,243 @@
+//! One-time initialization without poisoning
+//!
+//! These only need atomics, so they are available without `std`.
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::hint::spin_loop;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const INCOMPLETE: usize = 0;
+const RUNNING: usize = 1;
+const COMPLETE: usize = 2;
+
+/// A flag for running an initialization function exactly once.
+///
+/// Threads that find the function already running spin until it is
+/// complete. Unlike `std::sync::Once`, there is no poisoning: if the
+/// function panics, the flag is reset and the next caller runs it again.
+///
+/// ```
+/// use odds::sync::OnceFlag;
+///
+/// static INIT: OnceFlag = OnceFlag::new();
+///
+/// let mut count = 0;
+/// INIT.call_once_lite(|| count += 1);
+/// INIT.call_once_lite(|| count += 1);
+/// assert_eq!(count, 1);
+/// assert!(INIT.is_completed());
+/// ```
+pub struct OnceFlag {
+    state: AtomicUsize,
+}
+
+/// Resets the flag if the initialization function panics.
+struct ResetOnUnwind<'a> {
+    state: &'a AtomicUsize,
+}
+
+impl<'a> Drop for ResetOnUnwind<'a> {
+    fn drop(&mut self) {
+        self.state.store(INCOMPLETE, Ordering::Release);
+    }
+}
+
+impl OnceFlag {
+    /// Create a new flag, where the function has not run yet.
+    pub const fn new() -> Self {
+        OnceFlag {
+            state: AtomicUsize::new(INCOMPLETE),
+        }
+    }
+
+    /// Return `true` if a call to `.call_once_lite()` has completed.
+    pub fn is_completed(&self) -> bool {
+        self.state.load(Ordering::Acquire) == COMPLETE
+    }
+
+    /// Run `f` if no call has completed yet, otherwise do nothing.
+    ///
+    /// When this method returns, a call has completed, either this one or
+    /// one in another thread.
+    pub fn call_once_lite<F>(&self, f: F)
+        where F: FnOnce()
+    {
+        let mut f = Some(f);
+        loop {
+            match self.state.compare_exchange(INCOMPLETE, RUNNING,
+                                              Ordering::Acquire, Ordering::Acquire) {
+                Ok(_) => {
+                    let guard = ResetOnUnwind { state: &self.state };
+                    if let Some(f) = f.take() {
+                        f();
+                    }
+                    ::std::mem::forget(guard);
+                    self.state.store(COMPLETE, Ordering::Release);
+                    return;
+                }
+                Err(COMPLETE) => return,
+                Err(_) => spin_loop(),
+            }
+        }
+    }
+}
+
+impl Default for OnceFlag {
+    fn default() -> Self {
+        OnceFlag::new()
+    }
+}
+
+impl fmt::Debug for OnceFlag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OnceFlag")
+         .field("completed", &self.is_completed())
+         .finish()
+    }
+}
+
+/// A value that is computed by `init` on first access, for use in statics.
+///
+/// If `init` panics, the panic propagates to the caller and the next access
+/// runs `init` again.
+///
+/// ```
+/// use odds::sync::Lazy;
+///
+/// fn hex_digits() -> [u8; 256] {
+///     let mut table = [0xff; 256];
+///     for (i, &c) in b"0123456789abcdef".iter().enumerate() {
+///         table[c as usize] = i as u8;
+///     }
+///     table
+/// }
+///
+/// static HEX: Lazy<[u8; 256]> = Lazy::new(hex_digits);
+///
+/// assert_eq!(HEX[b'c' as usize], 12);
+/// assert_eq!(HEX[b'x' as usize], 0xff);
+/// ```
+pub struct Lazy<T, F = fn() -> T> {
+    once: OnceFlag,
+    init: UnsafeCell<Option<F>>,
+    value: UnsafeCell<Option<T>>,
+}
+
+unsafe impl<T, F> Sync for Lazy<T, F> where T: Send + Sync, F: Send { }
+
+impl<T, F> Lazy<T, F> {
+    /// Create a new `Lazy` that is computed by `init` on first access.
+    pub const fn new(init: F) -> Self {
+        Lazy {
+            once: OnceFlag::new(),
+            init: UnsafeCell::new(Some(init)),
+            value: UnsafeCell::new(None),
+        }
+    }
+}
+
+impl<T, F> Lazy<T, F>
+    where F: Fn() -> T
+{
+    /// Compute the value if it has not been computed yet, and return a
+    /// reference to it.
+    pub fn force(this: &Self) -> &T {
+        this.once.call_once_lite(|| {
+            unsafe {
+                // `init` is only dropped once it has returned, so that a
+                // panic leaves it in place for the next caller.
+                let value = match *this.init.get() {
+                    Some(ref init) => init(),
+                    None => ::debug_assert_unreachable(),
+                };
+                *this.value.get() = Some(value);
+                *this.init.get() = None;
+            }
+        });
+        unsafe {
+            match *this.value.get() {
+                Some(ref value) => value,
+                None => ::debug_assert_unreachable(),
+            }
+        }
+    }
+}
+
+impl<T, F> Deref for Lazy<T, F>
+    where F: Fn() -> T
+{
+    type Target = T;
+    fn deref(&self) -> &T {
+        Lazy::force(self)
+    }
+}
+
+impl<T, F> fmt::Debug for Lazy<T, F>
+    where T: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = if self.once.is_completed() {
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        };
+        f.debug_struct("Lazy")
+         .field("value", &value)
+         .finish()
+    }
+}
+
+#[test]
+fn test_once_flag_panic() {
+    use std::panic;
+
+    let flag = OnceFlag::new();
+    let res = panic::catch_unwind(|| flag.call_once_lite(|| panic!()));
+    assert!(res.is_err());
+    assert!(!flag.is_completed());
+    let mut ran = false;
+    flag.call_once_lite(|| ran = true);
+    assert!(ran);
+    assert!(flag.is_completed());
+}
+
+#[test]
+fn test_lazy_panic() {
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let calls = Cell::new(0);
+    let lazy = Lazy::new(|| {
+        calls.set(calls.get() + 1);
+        if calls.get() == 1 {
+            panic!("first call fails");
+        }
+        calls.get()
+    });
+    let res = panic::catch_unwind(AssertUnwindSafe(|| *Lazy::force(&lazy)));
+    assert!(res.is_err());
+    assert_eq!(*Lazy::force(&lazy), 2);
+    assert_eq!(*Lazy::force(&lazy), 2);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn test_lazy_threads() {
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static VALUE: Lazy<Vec<u32>> = Lazy::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        (0..100).collect()
+    });
+
+    let threads = (0..8).map(|_| thread::spawn(|| VALUE.iter().sum::<u32>()))
+                        .collect::<Vec<_>>();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 4950);
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,91 @@
+//! UTF-16 conversion without allocation
+
+use std::char::{decode_utf16, DecodeUtf16, REPLACEMENT_CHARACTER};
+use std::iter::Cloned;
+use std::slice;
+
+/// Encode `s` as UTF-16 into `buf`, and return the number of code units
+/// written.
+///
+/// If `buf` is too short, as many whole chars as fit are written; a
+/// surrogate pair is never split. The whole string was written if the
+/// result equals `s.encode_utf16().count()`.
+///
+/// ```
+/// use odds::utf16::encode_utf16_into;
+///
+/// let mut buf = [0u16; 8];
+/// let n = encode_utf16_into("a€𝄞", &mut buf);
+/// assert_eq!(&buf[..n], &[0x61, 0x20ac, 0xd834, 0xdd1e]);
+///
+/// // no room for the surrogate pair
+/// assert_eq!(encode_utf16_into("a€𝄞", &mut buf[..3]), 2);
+/// ```
+pub fn encode_utf16_into(s: &str, buf: &mut [u16]) -> usize {
+    let mut n = 0;
+    for c in s.chars() {
+        let width = c.len_utf16();
+        if n + width > buf.len() {
+            break;
+        }
+        c.encode_utf16(&mut buf[n..]);
+        n += width;
+    }
+    n
+}
+
+/// Return an iterator that decodes the UTF-16 `data` to chars, replacing
+/// each unpaired surrogate with U+FFFD.
+///
+/// ```
+/// use odds::utf16::decode_utf16_lossy;
+///
+/// let data = [0x68, 0xd834, 0xdd1e, 0xdc00, 0x69];
+/// let s = decode_utf16_lossy(&data).collect::<String>();
+/// assert_eq!(s, "h𝄞\u{fffd}i");
+/// ```
+pub fn decode_utf16_lossy<'a>(data: &'a [u16]) -> DecodeUtf16Lossy<'a> {
+    DecodeUtf16Lossy {
+        iter: decode_utf16(data.iter().cloned()),
+    }
+}
+
+/// An iterator of the chars of UTF-16 data, with unpaired surrogates
+/// replaced by U+FFFD.
+///
+/// See [`decode_utf16_lossy()`](fn.decode_utf16_lossy.html) for more
+/// information.
+#[derive(Clone, Debug)]
+pub struct DecodeUtf16Lossy<'a> {
+    iter: DecodeUtf16<Cloned<slice::Iter<'a, u16>>>,
+}
+
+impl<'a> Iterator for DecodeUtf16Lossy<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.iter.next().map(|r| r.unwrap_or(REPLACEMENT_CHARACTER))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[test]
+fn test_utf16_roundtrip() {
+    let s = "aß€𝄞\u{10ffff}\u{d7ff}\u{e000}";
+    let expected = s.encode_utf16().collect::<Vec<_>>();
+    let mut buf = [0u16; 16];
+    for len in 0..expected.len() + 2 {
+        let n = encode_utf16_into(s, &mut buf[..len]);
+        assert!(n <= len && len - n <= 1);
+        assert_eq!(&buf[..n], &expected[..n]);
+        let decoded = decode_utf16_lossy(&buf[..n]).collect::<String>();
+        assert!(s.starts_with(&decoded));
+        assert_eq!(decoded.encode_utf16().count(), n);
+    }
+    assert_eq!(decode_utf16_lossy(&[0xd800, 0xd800, 0xdc00]).collect::<String>(),
+               "\u{fffd}\u{10000}");
+    assert_eq!(decode_utf16_lossy(&[0xdbff]).collect::<String>(), "\u{fffd}");
+}
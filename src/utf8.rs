@@ -0,0 +1,317 @@
+//! UTF-8 validation and lossy decoding of bytes
+
+use std::cmp::min;
+use std::fmt;
+use std::str;
+
+/// An error from `Utf8Validator`: the stream is not valid UTF-8.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    valid_up_to: usize,
+}
+
+impl Utf8Error {
+    /// Return the number of bytes from the start of the stream that are
+    /// valid UTF-8, before the invalid sequence.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid utf-8 sequence at byte {}", self.valid_up_to)
+    }
+}
+
+/// An incremental UTF-8 validator, for a stream of bytes that arrives in
+/// chunks.
+///
+/// Chunks may split characters anywhere; the bytes of an incomplete
+/// character at the end of a chunk are kept until the next one.
+///
+/// ```
+/// use odds::utf8::Utf8Validator;
+///
+/// let text = "naïve café".as_bytes();
+/// let mut validator = Utf8Validator::new();
+/// // split inside the 'ï'
+/// assert!(validator.feed(&text[..3]).is_ok());
+/// assert_eq!(validator.pending(), 1);
+/// assert!(validator.feed(&text[3..]).is_ok());
+/// assert!(validator.finish().is_ok());
+///
+/// let mut validator = Utf8Validator::new();
+/// let err = validator.feed(b"ok\xffno").unwrap_err();
+/// assert_eq!(err.valid_up_to(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Utf8Validator {
+    /// The bytes of an incomplete character
+    buf: [u8; 4],
+    buf_len: usize,
+    /// The number of complete, valid bytes seen
+    valid: usize,
+    error: Option<Utf8Error>,
+}
+
+impl Utf8Validator {
+    /// Create a new validator, at the start of a stream.
+    pub fn new() -> Self {
+        Utf8Validator {
+            buf: [0; 4],
+            buf_len: 0,
+            valid: 0,
+            error: None,
+        }
+    }
+
+    /// Validate the next chunk of the stream.
+    ///
+    /// Once an error is found, it is returned again for every later chunk.
+    pub fn feed(&mut self, mut chunk: &[u8]) -> Result<(), Utf8Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if self.buf_len > 0 {
+            // complete the pending character first
+            let take = min(chunk.len(), 4 - self.buf_len);
+            let mut tmp = self.buf;
+            tmp[self.buf_len..self.buf_len + take].copy_from_slice(&chunk[..take]);
+            let tmp = &tmp[..self.buf_len + take];
+            match str::from_utf8(tmp) {
+                Err(ref e) if e.valid_up_to() == 0 => {
+                    if e.error_len().is_some() {
+                        return self.fail(0);
+                    }
+                    // still incomplete; the chunk was too short to finish it
+                    self.buf[self.buf_len..tmp.len()].copy_from_slice(&chunk[..take]);
+                    self.buf_len = tmp.len();
+                    return Ok(());
+                }
+                _ => {
+                    let width = char_width(self.buf[0]);
+                    chunk = &chunk[width - self.buf_len..];
+                    self.valid += width;
+                    self.buf_len = 0;
+                }
+            }
+        }
+        match str::from_utf8(chunk) {
+            Ok(_) => {
+                self.valid += chunk.len();
+                Ok(())
+            }
+            Err(e) => {
+                if e.error_len().is_some() {
+                    return self.fail(e.valid_up_to());
+                }
+                // an incomplete character at the end
+                let tail = &chunk[e.valid_up_to()..];
+                self.buf[..tail.len()].copy_from_slice(tail);
+                self.buf_len = tail.len();
+                self.valid += e.valid_up_to();
+                Ok(())
+            }
+        }
+    }
+
+    /// Return the number of bytes of an incomplete character at the end of
+    /// the input so far.
+    pub fn pending(&self) -> usize {
+        self.buf_len
+    }
+
+    /// Return the number of bytes of valid, complete characters so far.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid
+    }
+
+    /// End the stream, and return an error if it was invalid or ended with
+    /// an incomplete character.
+    pub fn finish(self) -> Result<(), Utf8Error> {
+        match self.error {
+            Some(err) => Err(err),
+            None if self.buf_len > 0 => Err(Utf8Error { valid_up_to: self.valid }),
+            None => Ok(()),
+        }
+    }
+
+    fn fail(&mut self, offset: usize) -> Result<(), Utf8Error> {
+        let err = Utf8Error { valid_up_to: self.valid + offset };
+        self.error = Some(err);
+        Err(err)
+    }
+}
+
+impl Default for Utf8Validator {
+    fn default() -> Self {
+        Utf8Validator::new()
+    }
+}
+
+/// Return the length of the encoded character starting with `lead`, which
+/// must be a valid leading byte.
+fn char_width(lead: u8) -> usize {
+    if lead < 0x80 {
+        1
+    } else if lead < 0xE0 {
+        2
+    } else if lead < 0xF0 {
+        3
+    } else {
+        4
+    }
+}
+
+/// Return an iterator that splits `bytes` into runs of valid UTF-8 and
+/// invalid sequences.
+///
+/// Valid runs are yielded as `Ok(&str)` and are never empty. Each invalid
+/// sequence is yielded separately as `Err(&[u8])`; replacing each of them
+/// by U+FFFD gives the same result as `String::from_utf8_lossy`, without
+/// allocating.
+///
+/// ```
+/// use odds::utf8::lossy_runs;
+///
+/// let runs = lossy_runs(b"caf\xc3\xa9 \xff\xfe ok").collect::<Vec<_>>();
+/// assert_eq!(runs, [Ok("café "), Err(&b"\xff"[..]), Err(&b"\xfe"[..]), Ok(" ok")]);
+///
+/// // escape invalid bytes instead of replacing them
+/// let mut out = String::new();
+/// for run in lossy_runs(b"a\x80b") {
+///     match run {
+///         Ok(s) => out.push_str(s),
+///         Err(bytes) => for b in bytes { out += &format!("\\x{:02x}", b); },
+///     }
+/// }
+/// assert_eq!(out, "a\\x80b");
+/// ```
+pub fn lossy_runs<'a>(bytes: &'a [u8]) -> LossyRuns<'a> {
+    LossyRuns {
+        bytes: bytes,
+        invalid: 0,
+    }
+}
+
+/// An iterator over runs of valid UTF-8 and invalid sequences in a byte
+/// slice.
+///
+/// Iterator element type is `Result<&'a str, &'a [u8]>`.
+///
+/// See [`lossy_runs`](fn.lossy_runs.html) for more information.
+#[derive(Clone, Debug)]
+pub struct LossyRuns<'a> {
+    bytes: &'a [u8],
+    /// The length of an invalid sequence at the start of `bytes`, if it
+    /// has already been found
+    invalid: usize,
+}
+
+impl<'a> LossyRuns<'a> {
+    /// Return the part of the input that is not yet iterated.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for LossyRuns<'a> {
+    type Item = Result<&'a str, &'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.invalid > 0 {
+            let (head, tail) = self.bytes.split_at(self.invalid);
+            self.bytes = tail;
+            self.invalid = 0;
+            return Some(Err(head));
+        }
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let (valid, invalid) = match str::from_utf8(self.bytes) {
+            Ok(_) => (self.bytes.len(), 0),
+            // an incomplete character at the end is one invalid sequence
+            Err(e) => (e.valid_up_to(),
+                       e.error_len().unwrap_or(self.bytes.len() - e.valid_up_to())),
+        };
+        self.invalid = invalid;
+        if valid == 0 {
+            return self.next();
+        }
+        let (head, tail) = self.bytes.split_at(valid);
+        self.bytes = tail;
+        unsafe {
+            Some(Ok(str::from_utf8_unchecked(head)))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bytes.len();
+        ((n != 0) as usize, Some(n))
+    }
+}
+
+#[test]
+fn test_lossy_runs() {
+    let inputs: &[&[u8]] = &[
+        b"", b"abc", b"\xff", b"a\xff", b"\xffa", b"\xe2\x82", b"a\xe2\x82\xffb\xe2\x82\xac",
+        b"\xf0\x9d\x84\x9e\xc0\x80\xed\xa0\x80x\xf0\x9d",
+    ];
+    for input in inputs {
+        let mut out = String::new();
+        let mut last_ok = false;
+        for run in lossy_runs(input) {
+            match run {
+                Ok(s) => {
+                    assert!(!s.is_empty() && !last_ok);
+                    last_ok = true;
+                    out.push_str(s);
+                }
+                Err(b) => {
+                    assert!(!b.is_empty());
+                    last_ok = false;
+                    out.push('\u{FFFD}');
+                }
+            }
+        }
+        assert_eq!(out, String::from_utf8_lossy(input), "{:?}", input);
+    }
+}
+
+#[test]
+fn test_validator_splits() {
+    let inputs: &[&[u8]] = &[
+        "aβ€𝄞z".as_bytes(),
+        b"ab\xce",
+        b"\xf0\x9d\x84",
+        b"a\xe2\x82\xffb",
+        b"\xc0\x80",
+        b"\xed\xa0\x80",
+        b"ok\xf4\x90\x80\x80",
+        b"\xe2\x82\xac\x80",
+    ];
+    for input in inputs {
+        let expected = str::from_utf8(input).map(|_| ()).map_err(|e| e.valid_up_to());
+        for i in 0..input.len() + 1 {
+            for j in i..input.len() + 1 {
+                let mut v = Utf8Validator::new();
+                let res = v.feed(&input[..i])
+                           .and_then(|_| v.feed(&input[i..j]))
+                           .and_then(|_| v.feed(&input[j..]))
+                           .and_then(|_| v.clone().finish());
+                assert_eq!(res.map_err(|e| e.valid_up_to()), expected,
+                           "{:?} split at {} {}", input, i, j);
+            }
+        }
+    }
+
+    let mut v = Utf8Validator::new();
+    assert!(v.feed(b"\xe2").is_ok());
+    assert!(v.feed(b"\x82").is_ok());
+    assert_eq!(v.pending(), 2);
+    assert_eq!(v.valid_up_to(), 0);
+    assert!(v.feed(b"\xacx").is_ok());
+    assert_eq!(v.pending(), 0);
+    assert_eq!(v.valid_up_to(), 4);
+}
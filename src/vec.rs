@@ -4,9 +4,11 @@
 #![cfg(feature="std")]
 
 use range::IndexRange;
+use std::mem;
 use std::ptr;
 use std::slice;
 
+use pointer::PointerExt;
 use slice::SliceFind;
 
 
@@ -86,7 +88,7 @@ impl<T> VecExt<T> for Vec<T> {
 
             // drop all elements in `r`
             {
-                let mslc = slice::from_raw_parts_mut(ptr.offset(r.start as isize), rm_len);
+                let mslc = slice::from_raw_parts_mut(ptr.debug_add(r.start), rm_len);
                 for elt_ptr in mslc {
                     ptr::read(elt_ptr); // Possible panic
                 }
@@ -94,15 +96,15 @@ impl<T> VecExt<T> for Vec<T> {
 
             if rm_len != input_len {
                 // move tail elements
-                ptr::copy(ptr.offset(r.end as isize),
-                          ptr.offset((r.start + input_len) as isize),
+                ptr::copy(ptr.debug_add(r.end),
+                          ptr.debug_add(r.start + input_len),
                           old_len - r.end);
             }
 
             // fill in elements from the iterator
             // FIXME: On panic, drop tail properly too (using panic guard)
             {
-                let grow_slc = slice::from_raw_parts_mut(ptr.offset(r.start as isize), input_len);
+                let grow_slc = slice::from_raw_parts_mut(ptr.debug_add(r.start), input_len);
                 let mut len = r.start;
                 for slot_ptr in grow_slc {
                     if let Some(input_elt) = iter.next() { // Possible Panic
@@ -176,6 +178,102 @@ impl<T> VecFindRemove for Vec<T> {
     }
 }
 
+/// A pair of buffers where one is read from while the other is filled.
+///
+/// This is the state machine of simulation steps and event queues: read
+/// from `.current()`, push the results of a step into `.next_mut()`, and
+/// then call `.swap_and_clear()` to make them the current contents.
+///
+/// Requires `feature="std"`
+///
+/// ```
+/// use odds::vec::DoubleBuffer;
+///
+/// let mut buf = DoubleBuffer::new();
+/// buf.next_mut().extend(vec![1, 2, 3]);
+/// buf.swap_and_clear();
+///
+/// for _ in 0..2 {
+///     let (current, next) = buf.split_mut();
+///     next.extend(current.iter().map(|&x| x * 10));
+///     buf.swap_and_clear();
+/// }
+/// assert_eq!(buf.current(), &[100, 200, 300]);
+/// assert!(buf.next_mut().is_empty());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DoubleBuffer<T> {
+    current: Vec<T>,
+    next: Vec<T>,
+}
+
+impl<T> DoubleBuffer<T> {
+    /// Create a new `DoubleBuffer` with two empty buffers.
+    pub fn new() -> Self {
+        DoubleBuffer {
+            current: Vec::new(),
+            next: Vec::new(),
+        }
+    }
+
+    /// Create a new `DoubleBuffer` where both buffers have room for at
+    /// least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        DoubleBuffer {
+            current: Vec::with_capacity(capacity),
+            next: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Return the current buffer's contents.
+    pub fn current(&self) -> &[T] {
+        &self.current
+    }
+
+    /// Return the current buffer's contents, mutably.
+    pub fn current_mut(&mut self) -> &mut [T] {
+        &mut self.current
+    }
+
+    /// Return the buffer being filled.
+    pub fn next_mut(&mut self) -> &mut Vec<T> {
+        &mut self.next
+    }
+
+    /// Return the current buffer's contents and the buffer being filled
+    /// at the same time.
+    pub fn split_mut(&mut self) -> (&mut [T], &mut Vec<T>) {
+        (&mut self.current, &mut self.next)
+    }
+
+    /// Make the buffer being filled the current buffer, and clear the old
+    /// current buffer so that it can be filled next.
+    ///
+    /// The allocations of both buffers are kept.
+    pub fn swap_and_clear(&mut self) {
+        mem::swap(&mut self.current, &mut self.next);
+        self.next.clear();
+    }
+
+    /// Return an iterator of the current buffer's elements.
+    pub fn iter(&self) -> slice::Iter<T> {
+        self.current.iter()
+    }
+
+    /// Return the current buffer and the buffer being filled.
+    pub fn into_inner(self) -> (Vec<T>, Vec<T>) {
+        (self.current, self.next)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoubleBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[test]
 fn test_splice() {
     use std::iter::once;
@@ -213,3 +311,20 @@ fn test_find() {
     assert_eq!(v.find_remove(&7), None);
     assert_eq!(&v, &[0, 1, 3, 1, 2]);
 }
+
+#[test]
+fn test_double_buffer() {
+    let mut buf = DoubleBuffer::with_capacity(4);
+    assert!(buf.current().is_empty());
+    buf.next_mut().push(1);
+    buf.swap_and_clear();
+    assert_eq!(buf.current(), &[1]);
+    buf.next_mut().push(2);
+    buf.next_mut().push(3);
+    assert_eq!(buf.current(), &[1]);
+    buf.swap_and_clear();
+    assert_eq!(buf.iter().cloned().collect::<Vec<_>>(), vec![2, 3]);
+    buf.swap_and_clear();
+    assert!(buf.current().is_empty());
+    assert_eq!(buf.into_inner(), (vec![], vec![]));
+}
@@ -18,6 +18,17 @@ fn mend_slices() {
     assert_eq!(words, vec!["α-toco", "(and)", "β-toco"]);
 }
 
+#[test]
+fn mend_slices_size_hint() {
+    let data = [1, 2, 3, 4];
+    let iter = data.chunks(1).mend_slices();
+    assert_eq!(iter.size_hint(), (1, Some(4)));
+    assert_eq!(iter.count(), 1);
+
+    let iter = data.chunks(1).filter(|_| false).mend_slices();
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
 #[test]
 fn mend_slices_mut() {
     let mut data = [1, 2, 3];
@@ -149,3 +149,51 @@ fn stride_index_fail() {
     let it = Stride::from_slice(xs, 2);
     let _ = it[2];
 }
+
+#[test]
+fn gather_scatter() {
+    use odds::stride::{gather, scatter, copy_strided};
+
+    let xs = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+    for step in &[1, 2, 3, -1, -4] {
+        for len in 0..12 {
+            let mut out = vec![0; len];
+            let n = gather(Stride::from_slice(&xs, *step), &mut out);
+            let expected = Stride::from_slice(&xs, *step).take(len).cloned().collect::<Vec<_>>();
+            assert_eq!(n, expected.len());
+            assert_eq!(&out[..n], &expected[..]);
+        }
+    }
+
+    let mut ys = [0; 11];
+    assert_eq!(scatter(&xs, StrideMut::from_slice(&mut ys, -2)), 6);
+    assert_eq!(ys, [6, 0, 5, 0, 4, 0, 3, 0, 2, 0, 1]);
+
+    let mut zs = [0; 11];
+    let n = copy_strided(Stride::from_slice(&ys, 2), StrideMut::from_slice(&mut zs[1..], 3));
+    assert_eq!(n, 4);
+    assert_eq!(zs, [0, 6, 0, 0, 5, 0, 0, 4, 0, 0, 3]);
+}
+
+#[test]
+fn gather_scatter_exhausted() {
+    use odds::stride::{gather, scatter, copy_strided};
+
+    let xs = [1, 2, 3, 4];
+    let mut src = Stride::from_slice(&xs, 3);
+    while let Some(_) = src.next() { }
+    let mut out = [0; 2];
+    assert_eq!(gather(src, &mut out), 0);
+
+    let mut ys = [0; 4];
+    let mut dst = StrideMut::from_slice(&mut ys, 3);
+    while let Some(_) = dst.next() { }
+    assert_eq!(scatter(&xs, dst), 0);
+
+    let src = Stride::from_slice(&xs, 3);
+    let mut zs = [0; 4];
+    let mut dst = StrideMut::from_slice(&mut zs, 3);
+    while let Some(_) = dst.next() { }
+    assert_eq!(copy_strided(src, dst), 0);
+    assert_eq!(out, [0, 0]);
+}